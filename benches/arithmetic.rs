@@ -0,0 +1,110 @@
+//! Host benchmarks of low-level arithmetic. The Karatsuba case is only
+//! measured with `--features karatsuba`.
+
+use bn254::arithmetic;
+use bn254::ff::Field;
+use bn254::Fr;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn bench_mul_wide(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let a = Fr::random(&mut rng);
+    let b = Fr::random(&mut rng);
+    let la = [1, 2, 3, 4].map(|i| u64::MAX / i);
+    let lb = [5, 6, 7, 8].map(|i| u64::MAX / i);
+
+    let mut group = c.benchmark_group("mul_wide");
+    group.bench_function("schoolbook", |bench| {
+        bench.iter(|| arithmetic::mul_wide_schoolbook(black_box(&la), black_box(&lb)))
+    });
+    #[cfg(feature = "karatsuba")]
+    group.bench_function("karatsuba", |bench| {
+        bench.iter(|| arithmetic::mul_wide_karatsuba(black_box(&la), black_box(&lb)))
+    });
+    group.bench_function("fr_mul", |bench| bench.iter(|| black_box(a) * black_box(b)));
+    group.finish();
+}
+
+/// `add_scaled` reduces the product and the accumulator together;
+/// `mul_then_add` is the unfused baseline of a Montgomery multiplication
+/// followed by a modular addition.
+fn bench_add_scaled(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let coeffs = Fr::random_vec(&mut rng, 1 << 12);
+    let points = Fr::random_vec(&mut rng, 1 << 12);
+
+    let mut group = c.benchmark_group("accumulate_4096");
+    group.bench_function("add_scaled", |bench| {
+        bench.iter(|| {
+            let mut acc = Fr::ZERO;
+            for (c, x) in coeffs.iter().zip(&points) {
+                acc.add_scaled(c, x);
+            }
+            black_box(acc)
+        })
+    });
+    group.bench_function("mul_then_add", |bench| {
+        bench.iter(|| {
+            let mut acc = Fr::ZERO;
+            for (c, x) in coeffs.iter().zip(&points) {
+                acc += c * x;
+            }
+            black_box(acc)
+        })
+    });
+    group.finish();
+}
+
+/// Compares the addition chain in `mul_by_u64` with the `Fr::from(k) * x`
+/// fallback for every `k` up to the chain limit of 16.
+fn bench_mul_by_u64(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let x = Fr::random(&mut rng);
+
+    let mut group = c.benchmark_group("mul_by_u64");
+    for k in 1..=16u64 {
+        group.bench_with_input(BenchmarkId::new("add_chain", k), &k, |bench, &k| {
+            bench.iter(|| black_box(x).mul_by_u64(black_box(k)))
+        });
+        group.bench_with_input(BenchmarkId::new("mul", k), &k, |bench, &k| {
+            bench.iter(|| Fr::from(black_box(k)) * black_box(x))
+        });
+    }
+    group.finish();
+}
+
+/// Batch inversion costs one inversion plus about three multiplications per
+/// element, so the per-element time should fall towards `3 * fr_mul` as the
+/// batch grows; `invert_each` is the unbatched baseline.
+fn bench_batch_invert(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let mut group = c.benchmark_group("batch_invert");
+    for n in [1, 10, 100, 1000, 10000] {
+        // Inverting in place repeatedly keeps every element nonzero.
+        let mut elements = Fr::random_vec(&mut rng, n);
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::new("batch_invert", n), &n, |bench, _| {
+            bench.iter(|| Fr::batch_invert(black_box(&mut elements)))
+        });
+        group.bench_with_input(BenchmarkId::new("invert_each", n), &n, |bench, _| {
+            bench.iter(|| {
+                for x in elements.iter_mut() {
+                    *x = x.invert().unwrap();
+                }
+                black_box(&elements);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_mul_wide,
+    bench_add_scaled,
+    bench_mul_by_u64,
+    bench_batch_invert
+);
+criterion_main!(benches);
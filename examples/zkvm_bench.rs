@@ -0,0 +1,71 @@
+//! Runs each basic `Fr` operation `ITERATIONS` times inside the SP1 zkVM.
+//!
+//! Every loop is wrapped in SP1's `cycle-tracker-start`/`cycle-tracker-end`
+//! markers, so running the program under the SP1 prover with cycle tracking
+//! enabled reports the cycles spent per operation. Divide by `ITERATIONS`
+//! for the per-call cost. Off the succinct target this example only prints
+//! a note.
+//!
+//! On the succinct target the example is `no_main` and the `sp1-zkvm`
+//! dev-dependency's `entrypoint!` wraps `main` as the guest entry point.
+#![cfg_attr(all(target_os = "zkvm", target_vendor = "succinct"), no_main)]
+
+#[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+sp1_zkvm::entrypoint!(main);
+
+#[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+mod bench {
+    use bn254::ff::Field;
+    use bn254::Fr;
+    use core::hint::black_box;
+
+    pub const ITERATIONS: usize = 1000;
+
+    fn track(label: &str, mut op: impl FnMut()) {
+        println!("cycle-tracker-start: {label}");
+        for _ in 0..ITERATIONS {
+            op();
+        }
+        println!("cycle-tracker-end: {label}");
+    }
+
+    pub fn run() {
+        let a = black_box(Fr::from_raw([
+            0x1234_5678_9abc_def0,
+            0x0fed_cba9_8765_4321,
+            0x1111_2222_3333_4444,
+            0x0555_6666_7777_8888,
+        ]));
+        let b = black_box(a.square() + Fr::ONE);
+
+        let mut acc = a;
+        track("add", || acc = black_box(acc + b));
+        track("mul", || acc = black_box(acc * b));
+        track("square", || acc = black_box(acc.square()));
+        track("invert", || acc = black_box(acc.invert().unwrap_or(b)));
+
+        // Compare `mul_by_u64` with the multiplication its addition chain
+        // replaces for every k up to the host limit of 16. Above the zkVM
+        // limit `mul_by_u64` takes the multiplication path itself, so the two
+        // trackers should agree there.
+        for k in 1..=16u64 {
+            track(&format!("mul_by_u64_{k}"), || {
+                acc = black_box(acc.mul_by_u64(black_box(k)))
+            });
+            track(&format!("mul_from_u64_{k}"), || {
+                acc = black_box(Fr::from(black_box(k)) * acc)
+            });
+        }
+        black_box(acc);
+    }
+}
+
+#[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+fn main() {
+    bench::run();
+}
+
+#[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+fn main() {
+    eprintln!("zkvm_bench only measures anything on the succinct zkVM target");
+}
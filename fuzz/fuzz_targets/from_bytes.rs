@@ -0,0 +1,11 @@
+#![no_main]
+
+use bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: [u8; 32]| {
+    // Anything accepted must be canonical, so it re-encodes to the same bytes.
+    if let Some(fr) = Option::<Fr>::from(Fr::from_bytes(&data)) {
+        assert_eq!(fr.to_bytes(), data);
+    }
+});
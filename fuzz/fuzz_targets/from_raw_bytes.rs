@@ -0,0 +1,18 @@
+#![no_main]
+
+use bn254::serde::SerdeObject;
+use bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let parsed = Fr::from_raw_bytes(data).ok();
+    if let Some(fr) = parsed {
+        assert_eq!(fr.to_raw_bytes(), data);
+    }
+
+    // The reader must agree with the slice parser and never panic.
+    let read = Fr::read_raw(&mut &data[..]).ok();
+    if data.len() == 32 {
+        assert_eq!(read, parsed);
+    }
+});
@@ -1,3 +1,6 @@
+use crate::Fr;
+use ff::PrimeField;
+
 /// Compute a + b + carry, returning the result and the new carry over.
 #[inline(always)]
 pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
@@ -18,3 +21,241 @@ pub(crate) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
     let ret = (a as u128) + ((b as u128) * (c as u128)) + (carry as u128);
     (ret as u64, (ret >> 64) as u64)
 }
+
+/// Subtracts the field modulus from `limbs` if and only if `limbs >= MODULUS`,
+/// in constant time. A single call fully reduces any input below
+/// `2 * MODULUS`, which makes it the final step of lazily reduced sums.
+pub fn conditional_subtract_modulus(limbs: &mut [u64; 4]) {
+    let modulus = Fr::modulus_limbs();
+    let (d0, borrow) = sbb(limbs[0], modulus[0], 0);
+    let (d1, borrow) = sbb(limbs[1], modulus[1], borrow);
+    let (d2, borrow) = sbb(limbs[2], modulus[2], borrow);
+    let (d3, borrow) = sbb(limbs[3], modulus[3], borrow);
+
+    // `borrow` is all ones iff `limbs < MODULUS`, in which case keep `limbs`.
+    limbs[0] = (d0 & !borrow) | (limbs[0] & borrow);
+    limbs[1] = (d1 & !borrow) | (limbs[1] & borrow);
+    limbs[2] = (d2 & !borrow) | (limbs[2] & borrow);
+    limbs[3] = (d3 & !borrow) | (limbs[3] & borrow);
+}
+
+/// Returns the canonical integer value of `a` as little-endian limbs.
+fn to_canonical_limbs(a: &Fr) -> [u64; 4] {
+    debug_assert!(a.is_canonical());
+    let repr = a.to_repr();
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(repr.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+/// Computes the unreduced 512-bit product of the canonical integer values of
+/// `a` and `b`, as little-endian limbs. Uses [`mul_wide_karatsuba`] when the
+/// `karatsuba` feature is enabled and [`mul_wide_schoolbook`] otherwise.
+pub fn mul_wide(a: &Fr, b: &Fr) -> [u64; 8] {
+    let a = to_canonical_limbs(a);
+    let b = to_canonical_limbs(b);
+    #[cfg(feature = "karatsuba")]
+    {
+        mul_wide_karatsuba(&a, &b)
+    }
+    #[cfg(not(feature = "karatsuba"))]
+    {
+        mul_wide_schoolbook(&a, &b)
+    }
+}
+
+/// Multiplies two 256-bit little-endian integers by schoolbook
+/// multiplication.
+pub fn mul_wide_schoolbook(a: &[u64; 4], b: &[u64; 4]) -> [u64; 8] {
+    let mut res = [0u64; 8];
+    for (i, &a) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &b) in b.iter().enumerate() {
+            (res[i + j], carry) = mac(res[i + j], a, b, carry);
+        }
+        res[i + 4] = carry;
+    }
+    res
+}
+
+/// Multiplies two 256-bit little-endian integers with one level of
+/// Karatsuba on 128-bit halves: three 128x128-bit products instead of four.
+/// Runs in constant time.
+#[cfg(feature = "karatsuba")]
+pub fn mul_wide_karatsuba(a: &[u64; 4], b: &[u64; 4]) -> [u64; 8] {
+    let (a0, a1) = ([a[0], a[1]], [a[2], a[3]]);
+    let (b0, b1) = ([b[0], b[1]], [b[2], b[3]]);
+
+    let z0 = mul_2x2(&a0, &b0);
+    let z2 = mul_2x2(&a1, &b1);
+
+    // mid = (a0 + a1)(b0 + b1), where each sum is a 128-bit value plus a carry
+    // bit worth 2^128.
+    let (sa, ca) = add_2(&a0, &a1);
+    let (sb, cb) = add_2(&b0, &b1);
+    let p = mul_2x2(&sa, &sb);
+    let mut mid = [p[0], p[1], p[2], p[3], ca & cb];
+    let (mask_a, mask_b) = (ca.wrapping_neg(), cb.wrapping_neg());
+    add_into(&mut mid[2..], &[sb[0] & mask_a, sb[1] & mask_a]);
+    add_into(&mut mid[2..], &[sa[0] & mask_b, sa[1] & mask_b]);
+
+    // z1 = mid - z0 - z2 = a0 * b1 + a1 * b0
+    sub_from(&mut mid, &z0);
+    sub_from(&mut mid, &z2);
+
+    let mut res = [z0[0], z0[1], z0[2], z0[3], z2[0], z2[1], z2[2], z2[3]];
+    add_into(&mut res[2..], &mid);
+    res
+}
+
+/// Multiplies two 128-bit little-endian integers.
+#[cfg(feature = "karatsuba")]
+fn mul_2x2(a: &[u64; 2], b: &[u64; 2]) -> [u64; 4] {
+    let (r0, carry) = mac(0, a[0], b[0], 0);
+    let (r1, r2) = mac(0, a[0], b[1], carry);
+    let (r1, carry) = mac(r1, a[1], b[0], 0);
+    let (r2, r3) = mac(r2, a[1], b[1], carry);
+    [r0, r1, r2, r3]
+}
+
+/// Adds two 128-bit little-endian integers, returning the sum and the carry.
+#[cfg(feature = "karatsuba")]
+fn add_2(a: &[u64; 2], b: &[u64; 2]) -> ([u64; 2], u64) {
+    let (s0, carry) = adc(a[0], b[0], 0);
+    let (s1, carry) = adc(a[1], b[1], carry);
+    ([s0, s1], carry)
+}
+
+/// Adds `x` into `acc`, propagating the carry through all of `acc`.
+#[cfg(feature = "karatsuba")]
+fn add_into(acc: &mut [u64], x: &[u64]) {
+    let mut carry = 0;
+    for (i, limb) in acc.iter_mut().enumerate() {
+        (*limb, carry) = adc(*limb, x.get(i).copied().unwrap_or(0), carry);
+    }
+}
+
+/// Subtracts `x` from `acc`, propagating the borrow through all of `acc`.
+#[cfg(feature = "karatsuba")]
+fn sub_from(acc: &mut [u64], x: &[u64]) {
+    let mut borrow = 0;
+    for (i, limb) in acc.iter_mut().enumerate() {
+        (*limb, borrow) = sbb(*limb, x.get(i).copied().unwrap_or(0), borrow);
+    }
+}
+
+/// Reduces a 512-bit little-endian integer, such as the output of
+/// [`mul_wide`], modulo the field modulus.
+pub fn reduce_from_wide_limbs(limbs: &[u64; 8]) -> Fr {
+    Fr::from_u64_digits(limbs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn to_biguint(limbs: &[u64]) -> BigUint {
+        BigUint::from_slice(
+            &limbs
+                .iter()
+                .flat_map(|limb| [*limb as u32, (*limb >> 32) as u32])
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_mul_wide() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..1000 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            let wide = mul_wide(&a, &b);
+            assert_eq!(reduce_from_wide_limbs(&wide), a * b);
+            assert_eq!(
+                to_biguint(&wide),
+                to_biguint(&to_canonical_limbs(&a)) * to_biguint(&to_canonical_limbs(&b))
+            );
+        }
+
+        let max = -Fr::ONE;
+        assert_eq!(reduce_from_wide_limbs(&mul_wide(&max, &max)), Fr::ONE);
+        assert_eq!(mul_wide(&Fr::ZERO, &max), [0; 8]);
+        assert_eq!(
+            mul_wide(&Fr::from(u64::MAX), &Fr::from(u64::MAX)),
+            [1, u64::MAX - 1, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[cfg(feature = "karatsuba")]
+    #[test]
+    fn test_mul_wide_karatsuba() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..5000 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            let (la, lb) = (to_canonical_limbs(&a), to_canonical_limbs(&b));
+            let wide = mul_wide_karatsuba(&la, &lb);
+            assert_eq!(wide, mul_wide_schoolbook(&la, &lb));
+            assert_eq!(reduce_from_wide_limbs(&wide), a * b);
+        }
+
+        // Carries out of both half sums
+        let max = [u64::MAX; 4];
+        assert_eq!(
+            mul_wide_karatsuba(&max, &max),
+            mul_wide_schoolbook(&max, &max)
+        );
+    }
+
+    #[test]
+    fn test_conditional_subtract_modulus() {
+        fn plus_modulus(x: &[u64; 4]) -> [u64; 4] {
+            let modulus = Fr::modulus_limbs();
+            let mut out = [0u64; 4];
+            let mut carry = 0;
+            for (i, limb) in out.iter_mut().enumerate() {
+                (*limb, carry) = adc(x[i], modulus[i], carry);
+            }
+            assert_eq!(carry, 0);
+            out
+        }
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut inputs: Vec<[u64; 4]> = (0..1000)
+            .map(|_| to_canonical_limbs(&Fr::random(&mut rng)))
+            .collect();
+        // Boundaries: 0, MODULUS - 1, and through `plus_modulus` MODULUS and
+        // 2 * MODULUS - 1.
+        inputs.push([0; 4]);
+        inputs.push(to_canonical_limbs(&-Fr::ONE));
+
+        for x in inputs {
+            // x in [0, MODULUS) is left alone ...
+            let mut limbs = x;
+            conditional_subtract_modulus(&mut limbs);
+            assert_eq!(limbs, x);
+
+            // ... and x + MODULUS in [MODULUS, 2 * MODULUS) comes back to x.
+            let mut limbs = plus_modulus(&x);
+            assert_eq!(
+                to_biguint(&limbs),
+                to_biguint(&x) + to_biguint(&Fr::modulus_limbs())
+            );
+            conditional_subtract_modulus(&mut limbs);
+            assert_eq!(limbs, x);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_mul_wide_non_canonical_panics() {
+        let _ = mul_wide(&Fr(Fr::modulus_limbs()), &Fr::ONE);
+    }
+}
@@ -0,0 +1,235 @@
+//! Constant-time modular inversion using the Bernstein–Yang "safegcd"
+//! algorithm, following the signed 62-bit limb layout and jump-matrix
+//! approach of libsecp256k1's `modinv64`.
+//!
+//! See <https://gcd.cr.yp.to/safegcd-20190413.pdf> and
+//! <https://github.com/bitcoin-core/secp256k1/blob/master/doc/safegcd_implementation.md>.
+
+/// Number of 62-bit limbs used to hold a signed integer of up to 310 bits.
+const LIMBS: usize = 5;
+
+/// Mask selecting the low 62 bits of a limb.
+const M62: i64 = (1 << 62) - 1;
+
+/// Number of divsteps batched into a single transition matrix.
+const DIVSTEPS: usize = 62;
+
+/// Number of outer iterations. Bernstein and Yang bound the number of divsteps
+/// needed for a 254-bit modulus by `(49 * 254 + 57) / 17 = 735`, and
+/// `12 * 62 = 744` covers that.
+const ITERATIONS: usize = 12;
+
+/// A signed integer in base `2^62`: the low limbs are in `[0, 2^62)` and the
+/// top limb carries the sign.
+type Signed62 = [i64; LIMBS];
+
+/// The transition matrix `[[u, v], [q, r]]` of a batch of divsteps, scaled by
+/// `2^62`.
+type Matrix = (i64, i64, i64, i64);
+
+/// Inverter for a fixed odd 256-bit modulus. The result of `invert` is
+/// multiplied by `adjuster`, which lets Montgomery-form callers pass `R^2` to
+/// get the inverse back in Montgomery form.
+#[derive(Debug)]
+pub(crate) struct BYInverter {
+    /// The modulus
+    modulus: Signed62,
+    /// The multiplier applied to every inverse
+    adjuster: Signed62,
+    /// `modulus^{-1} mod 2^62`
+    inverse: i64,
+}
+
+impl BYInverter {
+    /// Creates an inverter for the odd `modulus`, scaling results by
+    /// `adjuster` (which must be less than `modulus`).
+    pub(crate) const fn new(modulus: &[u64; 4], adjuster: &[u64; 4]) -> Self {
+        // Newton iteration for the inverse modulo 2^64: every odd `m` is its own
+        // inverse modulo 8, and each step doubles the number of correct bits.
+        let m = modulus[0];
+        let mut inv = m;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inv)));
+            i += 1;
+        }
+
+        BYInverter {
+            modulus: to_signed62(modulus),
+            adjuster: to_signed62(adjuster),
+            inverse: (inv as i64) & M62,
+        }
+    }
+
+    /// Returns `adjuster * value^{-1} mod modulus` for `value < modulus`, or
+    /// zero if `value` is zero. Runs in constant time.
+    pub(crate) fn invert(&self, value: &[u64; 4]) -> [u64; 4] {
+        let mut delta = 1;
+        let mut f = self.modulus;
+        let mut g = to_signed62(value);
+        let mut d = [0; LIMBS];
+        let mut e = self.adjuster;
+
+        for _ in 0..ITERATIONS {
+            let (next_delta, t) = divsteps(delta, f[0] as u64, g[0] as u64);
+            delta = next_delta;
+            update_fg(&mut f, &mut g, t);
+            self.update_de(&mut d, &mut e, t);
+        }
+
+        // `f` is now `±gcd(modulus, value)`, which is `±1` for nonzero input.
+        let sign = f[LIMBS - 1] >> 63;
+        from_signed62(&self.normalize(d, sign))
+    }
+
+    /// Applies `t / 2^62` to `(d, e)` modulo the modulus, adding multiples of
+    /// the modulus so that the division is exact. Keeps `d` and `e` in
+    /// `(-2 * modulus, modulus)`.
+    fn update_de(&self, d: &mut Signed62, e: &mut Signed62, t: Matrix) {
+        let (u, v, q, r) = t;
+        let sd = d[LIMBS - 1] >> 63;
+        let se = e[LIMBS - 1] >> 63;
+        let mut md = (u & sd) + (v & se);
+        let mut me = (q & sd) + (r & se);
+
+        let mut cd = u as i128 * d[0] as i128 + v as i128 * e[0] as i128;
+        let mut ce = q as i128 * d[0] as i128 + r as i128 * e[0] as i128;
+
+        // Choose `md` and `me` so that the low 62 bits of
+        // `t * [d, e] + modulus * [md, me]` vanish.
+        md -= (self.inverse.wrapping_mul(cd as i64).wrapping_add(md)) & M62;
+        me -= (self.inverse.wrapping_mul(ce as i64).wrapping_add(me)) & M62;
+
+        cd += self.modulus[0] as i128 * md as i128;
+        ce += self.modulus[0] as i128 * me as i128;
+        cd >>= 62;
+        ce >>= 62;
+
+        for i in 1..LIMBS {
+            cd += u as i128 * d[i] as i128
+                + v as i128 * e[i] as i128
+                + self.modulus[i] as i128 * md as i128;
+            ce += q as i128 * d[i] as i128
+                + r as i128 * e[i] as i128
+                + self.modulus[i] as i128 * me as i128;
+            d[i - 1] = (cd as i64) & M62;
+            e[i - 1] = (ce as i64) & M62;
+            cd >>= 62;
+            ce >>= 62;
+        }
+        d[LIMBS - 1] = cd as i64;
+        e[LIMBS - 1] = ce as i64;
+    }
+
+    /// Brings `value` from `(-2 * modulus, modulus)` into `[0, modulus)`,
+    /// negating it first if `sign` is `-1`.
+    fn normalize(&self, mut value: Signed62, sign: i64) -> Signed62 {
+        let add = value[LIMBS - 1] >> 63;
+        for (limb, m) in value.iter_mut().zip(self.modulus.iter()) {
+            *limb += m & add;
+            *limb = (*limb ^ sign) - sign;
+        }
+        propagate(&mut value);
+
+        let add = value[LIMBS - 1] >> 63;
+        for (limb, m) in value.iter_mut().zip(self.modulus.iter()) {
+            *limb += m & add;
+        }
+        propagate(&mut value);
+
+        value
+    }
+}
+
+/// Performs 62 constant-time divsteps on the low bits of `f` and `g`,
+/// returning the new `delta` and the scaled transition matrix.
+fn divsteps(mut delta: i64, f: u64, g: u64) -> (i64, Matrix) {
+    let (mut f, mut g) = (f as i64, g as i64);
+    let (mut u, mut v, mut q, mut r) = (1i64, 0i64, 0i64, 1i64);
+
+    for _ in 0..DIVSTEPS {
+        // `swap` is all ones iff `delta > 0` and `g` is odd.
+        let odd = -(g & 1);
+        let swap = (delta.wrapping_neg() >> 63) & odd;
+
+        // (delta, f, g, u, v, q, r) <- (-delta, g, -f, q, r, -u, -v)
+        let x = (f ^ g) & swap;
+        f ^= x;
+        g ^= x;
+        g = (g ^ swap).wrapping_sub(swap);
+        let x = (u ^ q) & swap;
+        u ^= x;
+        q ^= x;
+        q = (q ^ swap).wrapping_sub(swap);
+        let x = (v ^ r) & swap;
+        v ^= x;
+        r ^= x;
+        r = (r ^ swap).wrapping_sub(swap);
+        delta = (delta ^ swap).wrapping_sub(swap);
+
+        // If `g` is odd, add `f` to it so that it becomes even.
+        g = g.wrapping_add(f & odd);
+        q += u & odd;
+        r += v & odd;
+
+        delta += 1;
+        g >>= 1;
+        u <<= 1;
+        v <<= 1;
+    }
+
+    (delta, (u, v, q, r))
+}
+
+/// Replaces `(f, g)` with `t * [f, g] / 2^62`, where the division is exact.
+fn update_fg(f: &mut Signed62, g: &mut Signed62, t: Matrix) {
+    let (u, v, q, r) = t;
+    let mut cf = u as i128 * f[0] as i128 + v as i128 * g[0] as i128;
+    let mut cg = q as i128 * f[0] as i128 + r as i128 * g[0] as i128;
+    cf >>= 62;
+    cg >>= 62;
+
+    for i in 1..LIMBS {
+        cf += u as i128 * f[i] as i128 + v as i128 * g[i] as i128;
+        cg += q as i128 * f[i] as i128 + r as i128 * g[i] as i128;
+        f[i - 1] = (cf as i64) & M62;
+        g[i - 1] = (cg as i64) & M62;
+        cf >>= 62;
+        cg >>= 62;
+    }
+    f[LIMBS - 1] = cf as i64;
+    g[LIMBS - 1] = cg as i64;
+}
+
+/// Carries the excess of every limb into the next one, leaving all but the
+/// top limb in `[0, 2^62)`.
+fn propagate(value: &mut Signed62) {
+    for i in 1..LIMBS {
+        value[i] += value[i - 1] >> 62;
+        value[i - 1] &= M62;
+    }
+}
+
+/// Converts a 256-bit little-endian integer into base `2^62`.
+const fn to_signed62(a: &[u64; 4]) -> Signed62 {
+    let m = M62 as u64;
+    [
+        (a[0] & m) as i64,
+        ((a[0] >> 62 | a[1] << 2) & m) as i64,
+        ((a[1] >> 60 | a[2] << 4) & m) as i64,
+        ((a[2] >> 58 | a[3] << 6) & m) as i64,
+        (a[3] >> 56) as i64,
+    ]
+}
+
+/// Converts a non-negative base `2^62` integer below `2^256` back into 64-bit
+/// limbs.
+fn from_signed62(a: &Signed62) -> [u64; 4] {
+    let a = a.map(|limb| limb as u64);
+    [
+        a[0] | a[1] << 62,
+        a[1] >> 2 | a[2] << 60,
+        a[2] >> 4 | a[3] << 58,
+        a[3] >> 6 | a[4] << 56,
+    ]
+}
@@ -152,4 +152,171 @@ macro_rules! impl_binops_additive_specify_output {
         impl_add_binop_specify_output!($lhs, $rhs, $output);
         impl_sub_binop_specify_output!($lhs, $rhs, $output);
     };
-}
\ No newline at end of file
+}
+
+/// Implements the owned and mixed-reference forms of `Add` and `Sub`, together
+/// with `AddAssign` and `SubAssign` for owned and borrowed right-hand sides,
+/// for a type that already implements `Add` and `Sub` between two references.
+#[macro_export]
+macro_rules! impl_binops_additive {
+    ($lhs:ty, $rhs:ty) => {
+        impl ::core::ops::Add<$rhs> for $lhs {
+            type Output = $lhs;
+            #[inline]
+            fn add(self, rhs: $rhs) -> $lhs {
+                &self + &rhs
+            }
+        }
+
+        impl ::core::ops::Add<&$rhs> for $lhs {
+            type Output = $lhs;
+            #[inline]
+            fn add(self, rhs: &$rhs) -> $lhs {
+                &self + rhs
+            }
+        }
+
+        impl<'a> ::core::ops::Add<$rhs> for &'a $lhs {
+            type Output = $lhs;
+            #[inline]
+            fn add(self, rhs: $rhs) -> $lhs {
+                self + &rhs
+            }
+        }
+
+        impl ::core::ops::Sub<$rhs> for $lhs {
+            type Output = $lhs;
+            #[inline]
+            fn sub(self, rhs: $rhs) -> $lhs {
+                &self - &rhs
+            }
+        }
+
+        impl ::core::ops::Sub<&$rhs> for $lhs {
+            type Output = $lhs;
+            #[inline]
+            fn sub(self, rhs: &$rhs) -> $lhs {
+                &self - rhs
+            }
+        }
+
+        impl<'a> ::core::ops::Sub<$rhs> for &'a $lhs {
+            type Output = $lhs;
+            #[inline]
+            fn sub(self, rhs: $rhs) -> $lhs {
+                self - &rhs
+            }
+        }
+
+        $crate::impl_add_assign!($lhs, $rhs);
+        $crate::impl_sub_assign!($lhs, $rhs);
+    };
+}
+
+/// Implements `AddAssign` for owned and borrowed right-hand sides on top of the
+/// type's `Add` between two references.
+#[macro_export]
+macro_rules! impl_add_assign {
+    ($lhs:ty, $rhs:ty) => {
+        impl ::core::ops::AddAssign<$rhs> for $lhs {
+            #[inline]
+            fn add_assign(&mut self, rhs: $rhs) {
+                *self = &*self + &rhs;
+            }
+        }
+
+        impl ::core::ops::AddAssign<&$rhs> for $lhs {
+            #[inline]
+            fn add_assign(&mut self, rhs: &$rhs) {
+                *self = &*self + rhs;
+            }
+        }
+    };
+}
+
+/// Implements `SubAssign` for owned and borrowed right-hand sides on top of the
+/// type's `Sub` between two references.
+#[macro_export]
+macro_rules! impl_sub_assign {
+    ($lhs:ty, $rhs:ty) => {
+        impl ::core::ops::SubAssign<$rhs> for $lhs {
+            #[inline]
+            fn sub_assign(&mut self, rhs: $rhs) {
+                *self = &*self - &rhs;
+            }
+        }
+
+        impl ::core::ops::SubAssign<&$rhs> for $lhs {
+            #[inline]
+            fn sub_assign(&mut self, rhs: &$rhs) {
+                *self = &*self - rhs;
+            }
+        }
+    };
+}
+
+/// Implements `MulAssign` for owned and borrowed right-hand sides on top of the
+/// type's `Mul` between two references.
+#[macro_export]
+macro_rules! impl_mul_assign {
+    ($lhs:ty, $rhs:ty) => {
+        impl ::core::ops::MulAssign<$rhs> for $lhs {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $rhs) {
+                *self = &*self * &rhs;
+            }
+        }
+
+        impl ::core::ops::MulAssign<&$rhs> for $lhs {
+            #[inline]
+            fn mul_assign(&mut self, rhs: &$rhs) {
+                *self = &*self * rhs;
+            }
+        }
+    };
+}
+
+/// Builds a `[Fr; N]` from decimal string literals, parsing each with
+/// `PrimeField::from_str_vartime`. Panics on a literal that is not a decimal
+/// integer below the modulus. Uses the `ff` re-export, so it needs the
+/// `reexport` feature (on by default).
+///
+/// ```ignore
+/// let table = fr_vec!["0", "1", "21888242871839275222246405745257275088548364400416034343698204186575808495616"];
+/// ```
+#[macro_export]
+macro_rules! fr_vec {
+    ($($s:expr),* $(,)?) => {
+        [$(
+            <$crate::Fr as $crate::ff::PrimeField>::from_str_vartime($s)
+                .expect("invalid field element literal")
+        ),*]
+    };
+}
+
+/// Like `assert_eq!` for `Fr` operands, but a failure message shows both
+/// sides as canonical decimals (see `Fr::debug_decimal`) instead of limbs.
+#[macro_export]
+macro_rules! assert_fr_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if left != right {
+            panic!(
+                "assertion `left == right` failed\n  left: {}\n right: {}",
+                $crate::Fr::debug_decimal(left),
+                $crate::Fr::debug_decimal(right),
+            );
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let (left, right) = (&$left, &$right);
+        if left != right {
+            panic!(
+                "assertion `left == right` failed: {}\n  left: {}\n right: {}",
+                format_args!($($arg)+),
+                $crate::Fr::debug_decimal(left),
+                $crate::Fr::debug_decimal(right),
+            );
+        }
+    }};
+}
@@ -3,7 +3,9 @@ use crate::assembly::field_arithmetic_asm;
 #[cfg(not(feature = "asm"))]
 use crate::{field_arithmetic, field_specific};
 
-use crate::arithmetic::{adc, mac, sbb};
+use crate::arithmetic::{adc, conditional_subtract_modulus, mac, mul_wide_schoolbook, sbb};
+use crate::bernstein_yang::BYInverter;
+use crate::fr_common;
 use crate::{
     field_bits, field_common, impl_add_binop_specify_output, impl_binops_additive,
     impl_binops_additive_specify_output, impl_binops_multiplicative,
@@ -29,6 +31,7 @@ use serde::{Deserialize, Serialize};
 // Montgomery form; i.e., Fr(a) = aR mod r, with R = 2^256.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+#[repr(transparent)]
 pub struct Fr(pub(crate) [u64; 4]);
 
 /// Constant representing the modulus
@@ -103,12 +106,7 @@ const ROOT_OF_UNITY: Fr = Fr::from_raw([
 ]);
 
 /// 1 / 2 mod r
-const TWO_INV: Fr = Fr::from_raw([
-    0xa1f0fac9f8000001,
-    0x9419f4243cdcb848,
-    0xdc2822db40c0ac2e,
-    0x183227397098d014,
-]);
+const TWO_INV: Fr = Fr::from_raw(fr_common::TWO_INV_LIMBS);
 
 /// 1 / ROOT_OF_UNITY mod r
 const ROOT_OF_UNITY_INV: Fr = Fr::from_raw([
@@ -135,6 +133,10 @@ const ZETA: Fr = Fr::from_raw([
     0x00,
 ]);
 
+/// Inverter for the software path. `Fr` values are `aR`, so scaling the
+/// integer inverse by `R^2` gives `a^{-1}R` back in Montgomery form.
+const BYINVERTER: BYInverter = BYInverter::new(&MODULUS.0, &R2.0);
+
 impl_binops_additive!(Fr, Fr);
 impl_binops_multiplicative!(Fr, Fr);
 field_common!(
@@ -166,6 +168,186 @@ impl Fr {
     pub const fn size() -> usize {
         32
     }
+
+    /// Returns the field modulus as little-endian 64-bit limbs.
+    pub const fn modulus_limbs() -> [u64; 4] {
+        MODULUS.0
+    }
+
+    /// Returns whether the internal limbs are less than the modulus. Every
+    /// constructor of this type produces canonical limbs, so this only fails
+    /// for values corrupted through crate-internal access.
+    ///
+    /// This runs in variable time.
+    pub fn is_canonical(&self) -> bool {
+        Self::is_less_than(&self.0, &MODULUS.0)
+    }
+
+    /// Reduces the internal limbs of every element modulo the modulus, in
+    /// place. Elements built through this crate's constructors are already
+    /// reduced; this recovers ones loaded with
+    /// `SerdeObject::from_raw_bytes_unchecked` from stale or untrusted dumps.
+    pub fn batch_normalize(elements: &mut [Fr]) {
+        for element in elements.iter_mut() {
+            // 2^256 < 6 * MODULUS, so five conditional subtractions suffice.
+            for _ in 0..5 {
+                conditional_subtract_modulus(&mut element.0);
+            }
+        }
+    }
+
+    /// Inverts `self` without building a `CtOption`, for callers that already
+    /// know the input is nonzero. The result for zero input is unspecified.
+    pub fn invert_unchecked(&self) -> Fr {
+        debug_assert!(self.is_canonical());
+        Fr(BYINVERTER.invert(&self.0))
+    }
+
+    /// Returns `self * b + c` with a single Montgomery reduction. For `a`, `b`
+    /// and `c` held as `aR`, `bR` and `cR`, the 512-bit sum
+    /// `aR * bR + cR * 2^256` is `(ab + c)R^2`, which reduces straight to the
+    /// Montgomery form of `ab + c`.
+    pub(crate) fn fused_mul_add(&self, b: &Fr, c: &Fr) -> Fr {
+        debug_assert!(self.is_canonical() && b.is_canonical() && c.is_canonical());
+        let mut wide = mul_wide_schoolbook(&self.0, &b.0);
+        let mut carry = 0;
+        for (limb, c) in wide[4..].iter_mut().zip(c.0.iter()) {
+            (*limb, carry) = adc(*limb, *c, carry);
+        }
+        // The product is below MODULUS^2 and the addend below
+        // MODULUS * 2^256, so the sum fits in 512 bits.
+        debug_assert_eq!(carry, 0);
+        montgomery_reduce_wide(&wide)
+    }
+
+    /// Returns `Σ a[i] * b[i]`. The 512-bit products of the Montgomery forms
+    /// are added without reduction, and each run of `SUM_OF_PRODUCTS_CHUNK`
+    /// terms is reduced once.
+    pub(crate) fn sum_of_products(a: &[Fr], b: &[Fr]) -> Fr {
+        debug_assert_eq!(a.len(), b.len());
+        let mut sum = Fr::zero();
+        for (a, b) in a
+            .chunks(SUM_OF_PRODUCTS_CHUNK)
+            .zip(b.chunks(SUM_OF_PRODUCTS_CHUNK))
+        {
+            let mut wide = [0u64; 8];
+            for (a, b) in a.iter().zip(b) {
+                debug_assert!(a.is_canonical() && b.is_canonical());
+                let product = mul_wide_schoolbook(&a.0, &b.0);
+                let mut carry = 0;
+                for (limb, p) in wide.iter_mut().zip(product.iter()) {
+                    (*limb, carry) = adc(*limb, *p, carry);
+                }
+                debug_assert_eq!(carry, 0);
+            }
+            sum += montgomery_reduce_wide(&wide);
+        }
+        sum
+    }
+
+    /// Assigns `other` to `self` if `choice` is set, in constant time.
+    #[inline]
+    pub fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        <Self as ConditionallySelectable>::conditional_assign(self, other, choice)
+    }
+}
+
+impl ConditionallySelectable for Fr {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Fr([
+            u64::conditional_select(&a.0[0], &b.0[0], choice),
+            u64::conditional_select(&a.0[1], &b.0[1], choice),
+            u64::conditional_select(&a.0[2], &b.0[2], choice),
+            u64::conditional_select(&a.0[3], &b.0[3], choice),
+        ])
+    }
+
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        self.0[0].conditional_assign(&other.0[0], choice);
+        self.0[1].conditional_assign(&other.0[1], choice);
+        self.0[2].conditional_assign(&other.0[2], choice);
+        self.0[3].conditional_assign(&other.0[3], choice);
+    }
+}
+
+/// Terms summed unreduced by `Fr::sum_of_products` between reductions. Each
+/// product of canonical Montgomery limbs is below `MODULUS^2`, and eight of
+/// them stay below the `MODULUS * 2^257` that `montgomery_reduce_wide` takes.
+const SUM_OF_PRODUCTS_CHUNK: usize = 8;
+
+/// Montgomery-reduces a 512-bit integer below `MODULUS * 2^257`, returning
+/// `t * 2^-256 mod r` with canonical limbs. Unlike `Fr::montgomery_reduce`,
+/// which expects a single product, this allows the value to reach
+/// `3 * MODULUS` before the final subtractions, so `fused_mul_add` can pass
+/// a product and an addend at once.
+fn montgomery_reduce_wide(t: &[u64; 8]) -> Fr {
+    let mut r = *t;
+    let mut carry2 = 0;
+    for i in 0..4 {
+        let k = r[i].wrapping_mul(INV);
+        let mut carry = 0;
+        for (j, m) in MODULUS.0.iter().enumerate() {
+            (r[i + j], carry) = mac(r[i + j], k, *m, carry);
+        }
+        (r[i + 4], carry2) = adc(r[i + 4], carry2, carry);
+    }
+    debug_assert_eq!(carry2, 0);
+
+    let mut limbs = [r[4], r[5], r[6], r[7]];
+    conditional_subtract_modulus(&mut limbs);
+    conditional_subtract_modulus(&mut limbs);
+    Fr(limbs)
+}
+
+// The software routines assume canonical limbs and silently return wrong
+// results otherwise, so debug builds check their inputs.
+impl<'a> Neg for &'a Fr {
+    type Output = Fr;
+
+    #[inline]
+    fn neg(self) -> Fr {
+        debug_assert!(self.is_canonical());
+        self.neg()
+    }
+}
+
+impl Neg for Fr {
+    type Output = Fr;
+
+    #[inline]
+    fn neg(self) -> Fr {
+        -&self
+    }
+}
+
+impl<'a, 'b> Sub<&'b Fr> for &'a Fr {
+    type Output = Fr;
+
+    #[inline]
+    fn sub(self, rhs: &'b Fr) -> Fr {
+        debug_assert!(self.is_canonical() && rhs.is_canonical());
+        self.sub(rhs)
+    }
+}
+
+impl<'a, 'b> Add<&'b Fr> for &'a Fr {
+    type Output = Fr;
+
+    #[inline]
+    fn add(self, rhs: &'b Fr) -> Fr {
+        debug_assert!(self.is_canonical() && rhs.is_canonical());
+        self.add(rhs)
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fr> for &'a Fr {
+    type Output = Fr;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fr) -> Fr {
+        debug_assert!(self.is_canonical() && rhs.is_canonical());
+        self.mul(rhs)
+    }
 }
 
 impl ff::Field for Fr {
@@ -186,40 +368,31 @@ impl ff::Field for Fr {
     }
 
     fn double(&self) -> Self {
+        debug_assert!(self.is_canonical());
         self.double()
     }
 
     #[inline(always)]
     fn square(&self) -> Self {
+        debug_assert!(self.is_canonical());
         self.square()
     }
 
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
-        let tmp = self.pow([
-            0x43e1f593efffffff,
-            0x2833e84879b97091,
-            0xb85045b68181585d,
-            0x30644e72e131a029,
-        ]);
+        debug_assert!(self.is_canonical());
+        let tmp = Fr(BYINVERTER.invert(&self.0));
 
         CtOption::new(tmp, !self.ct_eq(&Self::zero()))
     }
 
     fn sqrt(&self) -> CtOption<Self> {
-        /// `(t - 1) // 2` where t * 2^s + 1 = p with t odd.
-        const T_MINUS1_OVER2: [u64; 4] = [
-            0xcdcb848a1f0fac9f,
-            0x0c0ac2e9419f4243,
-            0x098d014dc2822db4,
-            0x0000000183227397,
-        ];
-        ff::helpers::sqrt_tonelli_shanks(self, T_MINUS1_OVER2)
+        fr_common::sqrt(self)
     }
 
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
-        ff::helpers::sqrt_ratio_generic(num, div)
+        fr_common::sqrt_ratio(num, div)
     }
 }
 
@@ -279,6 +452,12 @@ impl ff::PrimeField for Fr {
     fn is_odd(&self) -> Choice {
         Choice::from(self.to_repr()[0] & 1)
     }
+
+    /// Builds the element from the two low limbs with a single conversion to
+    /// Montgomery form; `2^128 < MODULUS`, so no reduction is needed.
+    fn from_u128(v: u128) -> Self {
+        Fr::from_raw([v as u64, (v >> 64) as u64, 0, 0])
+    }
 }
 
 impl FromUniformBytes<64> for Fr {
@@ -300,4 +479,148 @@ impl FromUniformBytes<64> for Fr {
 
 impl WithSmallOrderMulGroup<3> for Fr {
     const ZETA: Self = ZETA;
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// `r - 2`, the Fermat inversion exponent.
+    const MODULUS_MINUS_TWO: [u64; 4] = [
+        0x43e1f593efffffff,
+        0x2833e84879b97091,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+    ];
+
+    #[test]
+    fn test_invert_matches_fermat() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..5000 {
+            let a = Fr::random(&mut rng);
+            let inv = a.invert().unwrap();
+            assert_eq!(inv, a.pow(MODULUS_MINUS_TWO));
+            assert_eq!(inv * a, Fr::one());
+        }
+
+        for a in [
+            Fr::one(),
+            -Fr::one(),
+            Fr::from(2u64),
+            TWO_INV,
+            ROOT_OF_UNITY,
+        ] {
+            assert_eq!(a.invert().unwrap(), a.pow(MODULUS_MINUS_TWO));
+        }
+        assert_eq!(TWO_INV.invert().unwrap(), Fr::from(2u64));
+        assert_eq!(ROOT_OF_UNITY.invert().unwrap(), ROOT_OF_UNITY_INV);
+    }
+
+    #[test]
+    fn test_invert_unchecked() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            assert_eq!(a.invert_unchecked(), a.invert().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_invert_zero() {
+        assert!(bool::from(Fr::zero().invert().is_none()));
+    }
+
+    #[test]
+    fn test_from_bytes_boundaries() {
+        let repr_of = |limbs: [u64; 4]| {
+            let mut bytes = [0u8; 32];
+            for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter()) {
+                chunk.copy_from_slice(&limb.to_le_bytes());
+            }
+            bytes
+        };
+        let m = MODULUS.0;
+
+        assert!(bool::from(Fr::from_bytes(&repr_of(m)).is_none()));
+        assert_eq!(
+            Fr::from_bytes(&repr_of([m[0] - 1, m[1], m[2], m[3]])).unwrap(),
+            -Fr::one()
+        );
+        assert_eq!(Fr::from_bytes(&[0u8; 32]).unwrap(), Fr::zero());
+        assert!(bool::from(Fr::from_bytes(&[0xff; 32]).is_none()));
+        assert!(bool::from(
+            Fr::from_bytes(&repr_of([m[0] + 1, m[1], m[2], m[3]])).is_none()
+        ));
+    }
+
+    #[test]
+    fn test_two_inv() {
+        assert_eq!(TWO_INV.double(), Fr::one());
+        assert_eq!(TWO_INV + TWO_INV, Fr::ONE);
+        assert_eq!(<Fr as PrimeField>::TWO_INV, TWO_INV);
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        assert!(Fr::zero().is_canonical());
+        assert!((-Fr::one()).is_canonical());
+        assert!(Fr(R.0).is_canonical());
+        assert!(!Fr(MODULUS.0).is_canonical());
+        assert!(!Fr([u64::MAX; 4]).is_canonical());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_non_canonical_arithmetic_panics() {
+        let _ = Fr(MODULUS.0) + Fr::one();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_non_canonical_invert_panics() {
+        let _ = Fr([u64::MAX; 4]).invert();
+    }
+
+    #[test]
+    fn test_batch_normalize() {
+        let five = Fr::from(5u64);
+        let (d0, carry) = adc(five.0[0], MODULUS.0[0], 0);
+        let (d1, carry) = adc(five.0[1], MODULUS.0[1], carry);
+        let (d2, carry) = adc(five.0[2], MODULUS.0[2], carry);
+        let (d3, _) = adc(five.0[3], MODULUS.0[3], carry);
+
+        let mut elements = [
+            Fr(MODULUS.0),
+            Fr([u64::MAX; 4]),
+            Fr([d0, d1, d2, d3]),
+            -Fr::one(),
+        ];
+        Fr::batch_normalize(&mut elements);
+
+        assert!(elements.iter().all(Fr::is_canonical));
+        assert_eq!(elements[0], Fr::zero());
+        // 2^256 - 1 = 5 * MODULUS + (R - 1)
+        assert_eq!(elements[1], Fr([R.0[0] - 1, R.0[1], R.0[2], R.0[3]]));
+        assert_eq!(elements[2], five);
+        assert_eq!(elements[3], -Fr::one());
+    }
+
+    #[test]
+    fn test_ct_eq_reduced_non_canonical() {
+        // R + MODULUS is a non-canonical representation of one.
+        let (d0, carry) = adc(R.0[0], MODULUS.0[0], 0);
+        let (d1, carry) = adc(R.0[1], MODULUS.0[1], carry);
+        let (d2, carry) = adc(R.0[2], MODULUS.0[2], carry);
+        let (d3, _) = adc(R.0[3], MODULUS.0[3], carry);
+        let one = Fr([d0, d1, d2, d3]);
+
+        assert!(!bool::from(one.ct_eq(&Fr::one())));
+        assert!(bool::from(one.ct_eq_reduced(&Fr::one())));
+        assert!(!bool::from(one.ct_eq_reduced(&Fr::zero())));
+    }
+}
@@ -0,0 +1,3204 @@
+// Helpers shared by the host (`fr.rs`) and zkVM (`fr_sp1.rs`) backends. Everything
+// here is written against the public `Fr` API and the `ff` traits so that it
+// behaves identically regardless of the internal limb representation.
+use crate::Fr;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Rem};
+use ff::{Field, PrimeField};
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// `(MODULUS - 1) / 2`, the exponent of Euler's criterion.
+const EXP_HALF: [u64; 4] = [
+    0xa1f0fac9f8000000,
+    0x9419f4243cdcb848,
+    0xdc2822db40c0ac2e,
+    0x183227397098d014,
+];
+
+/// `(t - 1) / 2` where `t * 2^S + 1 = MODULUS` with `t` odd.
+const T_MINUS1_OVER2: [u64; 4] = [
+    0xcdcb848a1f0fac9f,
+    0x0c0ac2e9419f4243,
+    0x098d014dc2822db4,
+    0x0000000183227397,
+];
+
+/// `1 / 2 mod MODULUS` as canonical limbs, `PrimeField::TWO_INV` on both
+/// backends.
+pub(crate) const TWO_INV_LIMBS: [u64; 4] = [
+    0xa1f0fac9f8000001,
+    0x9419f4243cdcb848,
+    0xdc2822db40c0ac2e,
+    0x183227397098d014,
+];
+
+/// `SMALL_ROOTS_OF_UNITY[k]` is the primitive `2^k`-th root of unity
+/// `ROOT_OF_UNITY^(2^(S - k))`.
+const SMALL_ROOTS_OF_UNITY: [Fr; 9] = [
+    Fr::from_raw([
+        0x0000000000000001,
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+    ]),
+    Fr::from_raw([
+        0x43e1f593f0000000,
+        0x2833e84879b97091,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+    ]),
+    Fr::from_raw([
+        0x231204708f703636,
+        0x5cea24f6fd736bec,
+        0x048b6e193fd84104,
+        0x30644e72e131a029,
+    ]),
+    Fr::from_raw([
+        0x948dad4ac1bd5e80,
+        0x52627366f8170a0a,
+        0xec9b9e2f96afef36,
+        0x2b337de1c8c14f22,
+    ]),
+    Fr::from_raw([
+        0xb11509c6e306460b,
+        0x996dfbe1174efb98,
+        0x1c6e4f4594dd508c,
+        0x21082ca216cbbf4e,
+    ]),
+    Fr::from_raw([
+        0x3eed4c533bb512d0,
+        0x9c18d51b838eeb1d,
+        0x9678200d47c0b2a9,
+        0x09c532c6306b93d2,
+    ]),
+    Fr::from_raw([
+        0x684cfa8ede70fdc7,
+        0xe18d1d2452d64bd2,
+        0xf6036a4037c3aa6a,
+        0x1c4c3a258629905e,
+    ]),
+    Fr::from_raw([
+        0x8c9e954b942e6d6b,
+        0x3df9cff80535c8d0,
+        0x49f7a010aeec0dae,
+        0x2822ef9d2d155c2b,
+    ]),
+    Fr::from_raw([
+        0x6692cc1e5a526c81,
+        0xd8675a9e5dd2336a,
+        0x820b96ff0a13f2db,
+        0x1058a83d529be585,
+    ]),
+];
+
+/// Largest multiplier handled by an addition chain in `mul_by_u64` on the
+/// host. The longest chain up to 16 is three doublings and three additions
+/// (for 15), each a handful of limb operations, while the fallback is a
+/// Montgomery multiplication plus another one to convert `k` into Montgomery
+/// form. The `mul_by_u64` group in `benches/arithmetic.rs` measures every `k`
+/// up to the limit against that fallback; rerun it before changing this.
+#[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+const MUL_BY_U64_ADD_CHAIN_LIMIT: u64 = 16;
+
+/// Largest multiplier handled by an addition chain in `mul_by_u64` in the
+/// zkVM. There `Fr::from(k)` is free and every addition, doubling and
+/// multiplication costs one syscall, so the chain only wins while it is a
+/// single doubling, i.e. for `k <= 2`. The `mul_by_u64_{k}` and
+/// `mul_from_u64_{k}` trackers in `examples/zkvm_bench.rs` compare the two
+/// paths per `k`; rerun them before changing this.
+#[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+const MUL_BY_U64_ADD_CHAIN_LIMIT: u64 = 2;
+
+/// Slices at most this long are summed on a single thread by `par_tree_sum`.
+#[cfg(feature = "rayon")]
+const PAR_TREE_SUM_THRESHOLD: usize = 1 << 12;
+
+/// `Field::sqrt` for both backends, by Tonelli-Shanks. The zkVM backend
+/// delegates here too, so the host tests exercise its square roots.
+pub(crate) fn sqrt(x: &Fr) -> CtOption<Fr> {
+    ff::helpers::sqrt_tonelli_shanks(x, T_MINUS1_OVER2)
+}
+
+/// `Field::sqrt_ratio` for both backends, built on [`sqrt`].
+pub(crate) fn sqrt_ratio(num: &Fr, div: &Fr) -> (Choice, Fr) {
+    ff::helpers::sqrt_ratio_generic(num, div)
+}
+
+/// Evaluates the polynomial with coefficients `coeffs`, lowest degree first,
+/// at `x`.
+fn horner(coeffs: &[Fr], x: &Fr) -> Fr {
+    coeffs.iter().rev().fold(Fr::ZERO, |acc, c| acc * x + c)
+}
+
+/// Compares two little-endian byte encodings as integers.
+fn repr_lt(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// Compares two little-endian byte encodings as integers in constant time.
+fn repr_ct_lt(a: &[u8; 32], b: &[u8; 32]) -> Choice {
+    let mut borrow = 0u16;
+    for (a, b) in a.iter().zip(b.iter()) {
+        let diff = (*a as u16).wrapping_sub(*b as u16).wrapping_sub(borrow);
+        borrow = diff >> 15;
+    }
+    Choice::from(borrow as u8)
+}
+
+/// Returns `table[index]` in constant time by scanning the whole table.
+fn linear_select(table: &[Fr], index: u8) -> Fr {
+    let mut res = Fr::ZERO;
+    for (i, entry) in table.iter().enumerate() {
+        res.conditional_assign(entry, (i as u8).ct_eq(&index));
+    }
+    res
+}
+
+/// Why [`Fr::from_u256_limbs_checked`] rejected a 256-bit integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    /// The integer is exactly the modulus, which reduces to zero.
+    EqualsModulus,
+    /// The integer is greater than the modulus.
+    AboveModulus,
+}
+
+impl core::fmt::Display for Boundary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Boundary::EqualsModulus => write!(f, "input number equals the field modulus"),
+            Boundary::AboveModulus => write!(f, "input number is greater than the field modulus"),
+        }
+    }
+}
+
+impl std::error::Error for Boundary {}
+
+impl Fr {
+    /// Returns the canonical little-endian bit decomposition of this element.
+    ///
+    /// Bit `i` of the result is bit `i % 64` of limb `i / 64` of the canonical
+    /// integer, so the array can be copied straight into a witness buffer.
+    pub fn to_bits_le(&self) -> [bool; 256] {
+        let repr = self.to_repr();
+        let mut bits = [false; 256];
+        for (i, bit) in bits.iter_mut().enumerate() {
+            *bit = (repr[i / 8] >> (i % 8)) & 1 == 1;
+        }
+        bits
+    }
+
+    /// Sums `elements` by recursively adding the sums of both halves. The
+    /// result equals `elements.iter().sum()`; the balanced shape keeps each
+    /// half cache-local and is what `par_tree_sum` splits across threads.
+    pub fn tree_sum(elements: &[Fr]) -> Fr {
+        match elements.len() {
+            0 => Fr::ZERO,
+            1 => elements[0],
+            n => {
+                let (left, right) = elements.split_at(n / 2);
+                Self::tree_sum(left) + Self::tree_sum(right)
+            }
+        }
+    }
+
+    /// Parallel version of `tree_sum`, splitting the slice across the rayon
+    /// thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_tree_sum(elements: &[Fr]) -> Fr {
+        if elements.len() <= PAR_TREE_SUM_THRESHOLD {
+            return Self::tree_sum(elements);
+        }
+        let (left, right) = elements.split_at(elements.len() / 2);
+        let (left, right) = rayon::join(|| Self::par_tree_sum(left), || Self::par_tree_sum(right));
+        left + right
+    }
+
+    /// Computes the linear combination `Σ coeffs[i] * points[i]` as a single
+    /// sum of products. On the host the 512-bit products are accumulated
+    /// unreduced and reduced once per eight terms; in the zkVM, where every
+    /// syscall reduces its own output, each term is one [`Fr::mul_add`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coeffs` and `points` have different lengths.
+    pub fn linear_combination(coeffs: &[Fr], points: &[Fr]) -> Fr {
+        assert_eq!(coeffs.len(), points.len());
+        #[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+        {
+            Fr::sum_of_products(coeffs, points)
+        }
+        #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+        {
+            coeffs
+                .iter()
+                .zip(points.iter())
+                .fold(Fr::ZERO, |acc, (c, x)| c.mul_add(x, &acc))
+        }
+    }
+
+    /// Recomposes a little-endian bit decomposition into a field element, the
+    /// inverse of `to_bits_le`. Inputs of any length are accepted and the
+    /// integer they encode is reduced modulo the field modulus, so bit
+    /// patterns at or above the modulus wrap around.
+    pub fn from_le_bits(bits: &[bool]) -> Fr {
+        bits.iter()
+            .rev()
+            .fold(Fr::ZERO, |acc, &bit| acc.double() + Fr::from(bit))
+    }
+
+    /// Multiplies `self` by the integer `k`.
+    ///
+    /// For `k <= MUL_BY_U64_ADD_CHAIN_LIMIT` this uses a double-and-add chain
+    /// starting from `self`, one doubling per bit below the top set bit of
+    /// `k` and one addition per further set bit; larger `k` fall back to
+    /// `Fr::from(k) * self`. The limit differs per backend, see its docs. The
+    /// running time depends on `k`, which is assumed to be a public constant.
+    pub fn mul_by_u64(&self, k: u64) -> Fr {
+        if k > MUL_BY_U64_ADD_CHAIN_LIMIT {
+            return Fr::from(k) * self;
+        }
+        if k == 0 {
+            return Fr::ZERO;
+        }
+
+        let mut acc = *self;
+        for i in (0..63 - k.leading_zeros()).rev() {
+            acc = acc.double();
+            if (k >> i) & 1 == 1 {
+                acc += self;
+            }
+        }
+        acc
+    }
+
+    /// Inverts every element of `elements` in place using Montgomery's trick,
+    /// with `scratch` (of the same length) holding the prefix products so that
+    /// no allocation is needed. Costs one inversion and `3(n - 1)`
+    /// multiplications.
+    ///
+    /// Returns none, leaving `elements` unchanged, if any element is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `elements` and `scratch` have different lengths.
+    pub fn batch_invert_in(elements: &mut [Fr], scratch: &mut [Fr]) -> CtOption<()> {
+        assert_eq!(elements.len(), scratch.len());
+
+        let mut acc = Fr::ONE;
+        for (x, prefix) in elements.iter().zip(scratch.iter_mut()) {
+            *prefix = acc;
+            acc *= x;
+        }
+
+        let inv = acc.invert();
+        let is_some = inv.is_some();
+        let mut acc = inv.unwrap_or(Fr::ZERO);
+        for (x, prefix) in elements.iter_mut().zip(scratch.iter()).rev() {
+            let next = acc * *x;
+            x.conditional_assign(&(acc * prefix), is_some);
+            acc = next;
+        }
+
+        CtOption::new((), is_some)
+    }
+
+    /// Returns the width-`window` non-adjacent form of the canonical integer
+    /// value of `self`, least significant digit first. Every nonzero digit is
+    /// odd and less than `2^(window - 1)` in absolute value, and any `window`
+    /// consecutive digits contain at most one nonzero digit.
+    ///
+    /// This runs in variable time and must only be used on public scalars.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `2 <= window <= 8`.
+    pub fn to_wnaf(&self, window: usize) -> Vec<i8> {
+        assert!((2..=8).contains(&window));
+
+        let repr = self.to_repr();
+        let mut k = [0u64; 5];
+        for (limb, chunk) in k.iter_mut().zip(repr.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let width = 1i64 << window;
+        let mut digits = Vec::with_capacity(257);
+        while k.iter().any(|&limb| limb != 0) {
+            let mut digit = 0i64;
+            if k[0] & 1 == 1 {
+                digit = (k[0] & (width as u64 - 1)) as i64;
+                if digit >= width / 2 {
+                    digit -= width;
+                }
+
+                // k -= digit
+                let (mut borrow, mut carry) = (0u64, 0u64);
+                if digit > 0 {
+                    borrow = digit as u64;
+                } else {
+                    carry = digit.unsigned_abs();
+                }
+                for limb in k.iter_mut() {
+                    let (sub, b) = limb.overflowing_sub(borrow);
+                    let (add, c) = sub.overflowing_add(carry);
+                    *limb = add;
+                    borrow = b as u64;
+                    carry = c as u64;
+                }
+            }
+            digits.push(digit as i8);
+
+            for i in 0..4 {
+                k[i] = (k[i] >> 1) | (k[i + 1] << 63);
+            }
+            k[4] >>= 1;
+        }
+        digits
+    }
+
+    /// Computes `self^((MODULUS - 1) / 2)` in constant time. By Euler's
+    /// criterion this is `1` for nonzero squares, `-1` for non-squares and `0`
+    /// for zero.
+    pub fn pow_half_order(&self) -> Fr {
+        self.pow(EXP_HALF)
+    }
+
+    /// Converts an arbitrary-length little-endian base `2^64` integer into a
+    /// field element, reducing it modulo the field modulus. An empty slice
+    /// encodes zero.
+    pub fn from_u64_digits(digits: &[u64]) -> Fr {
+        // 2^64
+        let base = Fr::from_raw([0, 1, 0, 0]);
+        digits
+            .iter()
+            .rev()
+            .fold(Fr::ZERO, |acc, &digit| acc * base + Fr::from(digit))
+    }
+
+    /// Returns the field modulus as a 32-byte big-endian integer.
+    pub fn modulus_bytes_be() -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes
+            .chunks_exact_mut(8)
+            .zip(Fr::modulus_limbs().iter().rev())
+        {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Sets `out[i]` to `a[i]` if `choice` is set and to `b[i]` otherwise, in
+    /// constant time. The operands read like `if choice { a } else { b }`,
+    /// which is the reverse of the argument order of `conditional_select`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a`, `b` and `out` do not all have the same length.
+    pub fn conditional_select_slice(a: &[Fr], b: &[Fr], out: &mut [Fr], choice: Choice) {
+        assert_eq!(a.len(), b.len());
+        assert_eq!(a.len(), out.len());
+        for ((out, a), b) in out.iter_mut().zip(a.iter()).zip(b.iter()) {
+            *out = Fr::conditional_select(b, a, choice);
+        }
+    }
+
+    /// Samples an element whose canonical integer value is uniformly
+    /// distributed in `[lo, hi)`, by rejection sampling on the width of the
+    /// interval. Returns `lo` if `lo == hi`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo > hi` as integers.
+    pub fn random_in_range(mut rng: impl RngCore, lo: &Fr, hi: &Fr) -> Fr {
+        if lo == hi {
+            return *lo;
+        }
+        assert!(repr_lt(&lo.to_repr(), &hi.to_repr()));
+
+        // `hi - lo` does not wrap, so it is the integer width of the interval.
+        let width = (hi - lo).to_repr();
+        let top = width.iter().rposition(|&b| b != 0).unwrap();
+        let mask = u8::MAX >> width[top].leading_zeros();
+
+        let mut bytes = [0u8; 32];
+        loop {
+            rng.fill_bytes(&mut bytes[..=top]);
+            bytes[top] &= mask;
+            if repr_lt(&bytes, &width) {
+                return lo + Fr::from_repr(bytes).unwrap();
+            }
+        }
+    }
+
+    /// Squares `self` `k` times, computing `self^(2^k)`.
+    pub fn pow_2k(&self, k: u32) -> Fr {
+        (0..k).fold(*self, |acc, _| acc.square())
+    }
+
+    /// Returns the primitive `2^k`-th root of unity `ROOT_OF_UNITY^(2^(S - k))`.
+    /// Values for `k <= 8` come from a precomputed table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > S`.
+    pub fn root_of_unity(k: u32) -> Fr {
+        assert!(k <= Fr::S);
+        match SMALL_ROOTS_OF_UNITY.get(k as usize) {
+            Some(root) => *root,
+            None => Fr::ROOT_OF_UNITY.pow_2k(Fr::S - k),
+        }
+    }
+
+    /// Recodes the canonical integer value of `self` into 64 signed radix-16
+    /// digits `d_i`, least significant first, with `self = Σ d_i * 16^i`.
+    ///
+    /// The nibbles of the canonical encoding are recentred from `[0, 16)` to
+    /// `[-8, 8)` by carrying `1` into the next digit whenever a digit is at
+    /// least `8`, as in curve25519-dalek's `as_radix_16`. Every digit except
+    /// the last lies in `[-8, 8)`; the last absorbs the final carry and, since
+    /// the modulus is below `2^254`, lies in `[0, 4]`. The recoding has no
+    /// data-dependent branches.
+    pub fn to_radix_16(&self) -> [i8; 64] {
+        let bytes = self.to_repr();
+        let mut digits = [0i8; 64];
+        for (i, byte) in bytes.iter().enumerate() {
+            digits[2 * i] = (byte & 15) as i8;
+            digits[2 * i + 1] = (byte >> 4) as i8;
+        }
+
+        for i in 0..63 {
+            let carry = (digits[i] + 8) >> 4;
+            digits[i] -= carry << 4;
+            digits[i + 1] += carry;
+        }
+        digits
+    }
+
+    /// Converts an arkworks BN254 scalar into `Fr`. Both crates store scalars
+    /// differently internally, so the conversion goes through the canonical
+    /// integer value.
+    #[cfg(feature = "ark")]
+    pub fn from_ark(value: &ark_bn254::Fr) -> Fr {
+        use ark_ff::PrimeField as _;
+        Fr::from_raw(value.into_bigint().0)
+    }
+
+    /// Converts `self` into an arkworks BN254 scalar with the same value.
+    #[cfg(feature = "ark")]
+    pub fn to_ark(&self) -> ark_bn254::Fr {
+        use ark_ff::PrimeField as _;
+        let repr = self.to_repr();
+        let limbs = [0, 8, 16, 24].map(|i| u64::from_le_bytes(repr[i..i + 8].try_into().unwrap()));
+        ark_bn254::Fr::from_bigint(ark_ff::BigInt::new(limbs)).unwrap()
+    }
+
+    /// Computes a square root of `self`, or `None` if `self` is not a square.
+    ///
+    /// This is Tonelli–Shanks with data-dependent loop bounds: it returns as
+    /// soon as it detects a non-residue and skips the work for roots of low
+    /// 2-adic order. Its running time therefore leaks information about
+    /// `self`, so it must only be used on public values such as openings being
+    /// verified. Use `Field::sqrt` for secret inputs.
+    pub fn sqrt_vartime(&self) -> Option<Fr> {
+        if *self == Fr::ZERO {
+            return Some(Fr::ZERO);
+        }
+
+        let w = self.pow_vartime(T_MINUS1_OVER2);
+        // x = self^((t + 1) / 2), b = self^t
+        let mut x = self * w;
+        let mut b = x * w;
+        let mut z = Fr::ROOT_OF_UNITY;
+        let mut m = Fr::S;
+
+        while b != Fr::ONE {
+            // Find the least `i` with `b^(2^i) = 1`; `self` is a non-residue
+            // exactly when no such `i < m` exists.
+            let mut i = 1;
+            let mut b2 = b.square();
+            while b2 != Fr::ONE {
+                if i == m - 1 {
+                    return None;
+                }
+                b2 = b2.square();
+                i += 1;
+            }
+
+            let c = z.pow_2k(m - i - 1);
+            z = c.square();
+            x *= c;
+            b *= z;
+            m = i;
+        }
+
+        Some(x)
+    }
+
+    /// Returns `self * b + c`, the multiply-accumulate used throughout
+    /// constraint evaluation. On the host the addend joins the 512-bit product
+    /// and the sum is reduced once; in the zkVM each operation is a syscall
+    /// that reduces its own output, so this is a multiplication and an
+    /// addition.
+    pub fn mul_add(&self, b: &Fr, c: &Fr) -> Fr {
+        #[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+        {
+            self.fused_mul_add(b, c)
+        }
+        #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+        {
+            self * b + c
+        }
+    }
+
+    /// Returns `self * b - c`, computed as `self * b + (-c)` with the same
+    /// single reduction as [`Fr::mul_add`] on the host.
+    pub fn mul_sub(&self, b: &Fr, c: &Fr) -> Fr {
+        self.mul_add(b, &-c)
+    }
+
+    /// Writes `input[i]^2` to `out[i]`, using the dedicated squaring routine
+    /// for every element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `out` have different lengths.
+    pub fn square_batch(input: &[Fr], out: &mut [Fr]) {
+        assert_eq!(input.len(), out.len(), "length mismatch");
+        for (o, x) in out.iter_mut().zip(input) {
+            *o = x.square();
+        }
+    }
+
+    /// Returns the inverse of `self` together with a flag that is set iff
+    /// `self` is nonzero. For zero the returned element is `ZERO`. Runs in
+    /// constant time.
+    pub fn invert_flagged(&self) -> (Fr, Choice) {
+        let inv = self.invert();
+        (inv.unwrap_or(Fr::ZERO), inv.is_some())
+    }
+
+    /// Raises `self` to a `u64` power. Variable time in `exp`.
+    pub fn pow_vartime_u64(&self, exp: u64) -> Fr {
+        self.pow_vartime([exp])
+    }
+
+    /// Shifts the canonical integer value of `self` right by `n` bits,
+    /// discarding the low bits. Variable time in `n`.
+    pub fn shr_vartime(&self, n: u32) -> Fr {
+        let repr = self.to_repr();
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(repr.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let words = (n / 64) as usize;
+        let bits = n % 64;
+        let mut shifted = [0u64; 4];
+        for (i, out) in shifted
+            .iter_mut()
+            .take(4usize.saturating_sub(words))
+            .enumerate()
+        {
+            *out = limbs[i + words] >> bits;
+            if bits > 0 && i + words + 1 < 4 {
+                *out |= limbs[i + words + 1] << (64 - bits);
+            }
+        }
+        // The result is below `self`, so it is already canonical.
+        Fr::from_raw(shifted)
+    }
+
+    /// Shifts the canonical integer value of `self` left by `n` bits and
+    /// reduces the result, i.e. returns `self * 2^n`. Variable time in `n`.
+    pub fn shl_vartime(&self, n: u32) -> Fr {
+        self * Fr::from(2u64).pow_vartime_u64(n as u64)
+    }
+
+    /// Returns whether the multiplicative order of `self` divides the
+    /// little-endian integer `n`, i.e. whether `self^n == 1`. Variable time;
+    /// intended for validating public constants such as generators and
+    /// roots of unity.
+    pub fn has_order_dividing(&self, n: &[u64]) -> bool {
+        self.pow_vartime(n) == Fr::ONE
+    }
+
+    /// Returns the centered representative of `self` as a sign and a
+    /// big-endian magnitude.
+    ///
+    /// The centered representative is the unique integer `c` congruent to
+    /// `self` in `[-(MODULUS - 1) / 2, (MODULUS - 1) / 2]`; the modulus is odd,
+    /// so this is the same range as `(-MODULUS / 2, MODULUS / 2]`. The sign is
+    /// set iff `c < 0`, and the magnitude is `|c|`. Runs in constant time.
+    pub fn to_centered_bytes_be(&self) -> (Choice, [u8; 32]) {
+        let neg = -self;
+        // `self > (MODULUS - 1) / 2` exactly when its negation is smaller.
+        let is_negative = repr_ct_lt(&neg.to_repr(), &self.to_repr());
+        let mut magnitude = Fr::conditional_select(self, &neg, is_negative).to_repr();
+        magnitude.reverse();
+        (is_negative, magnitude)
+    }
+
+    /// Returns the canonical little-endian encoding of `self`, reducing the
+    /// internal limbs first. Unlike `to_repr` on limbs that were loaded
+    /// without reduction, `from_repr(x.to_repr_reduced())` always succeeds.
+    pub fn to_repr_reduced(&self) -> [u8; 32] {
+        let mut reduced = [*self];
+        Fr::batch_normalize(&mut reduced);
+        reduced[0].to_repr()
+    }
+
+    /// Raises `self` to the little-endian exponent `exp` with a fixed 4-bit
+    /// window. Every window performs the same squarings, one multiplication
+    /// and a full table scan, so the running time is independent of both
+    /// `self` and `exp`.
+    pub fn pow_ct(&self, exp: &[u64; 4]) -> Fr {
+        // table[i] = self^i
+        let mut table = [Fr::ONE; 16];
+        let mut power = Fr::ONE;
+        for entry in table.iter_mut().skip(1) {
+            power *= self;
+            *entry = power;
+        }
+
+        let mut res = Fr::ONE;
+        for limb in exp.iter().rev() {
+            for shift in (0..16).rev() {
+                res = res.square().square().square().square();
+                let nibble = ((limb >> (shift * 4)) & 0xf) as u8;
+                res *= linear_select(&table, nibble);
+            }
+        }
+        res
+    }
+
+    /// Returns the inverse of `self`, or `ONE` if `self` is zero. Runs in
+    /// constant time.
+    pub fn invert_or_one(&self) -> Fr {
+        self.invert().unwrap_or(Fr::ONE)
+    }
+
+    /// Inverts every nonzero element of `elements` in place and leaves zeros
+    /// as they are, using Montgomery's trick. Zeros enter the product chain
+    /// as `ONE`, so a single zero does not spoil the others. Runs in constant
+    /// time.
+    pub fn batch_invert(elements: &mut [Fr]) {
+        let mut prefixes = Vec::with_capacity(elements.len());
+        let mut acc = Fr::ONE;
+        for x in elements.iter() {
+            prefixes.push(acc);
+            acc *= Fr::conditional_select(x, &Fr::ONE, x.is_zero());
+        }
+
+        let mut acc = acc.invert_or_one();
+        for (x, prefix) in elements.iter_mut().zip(prefixes.iter()).rev() {
+            let is_zero = x.is_zero();
+            let next = acc * Fr::conditional_select(x, &Fr::ONE, is_zero);
+            x.conditional_assign(&(acc * prefix), !is_zero);
+            acc = next;
+        }
+    }
+
+    /// Packs two 128-bit values into the element `lo + hi * 2^128`. Returns
+    /// none if that integer is not less than the modulus, which can only
+    /// happen when `hi` uses more than 125 bits.
+    pub fn pack_u128_pair(lo: u128, hi: u128) -> CtOption<Fr> {
+        let mut repr = [0u8; 32];
+        repr[..16].copy_from_slice(&lo.to_le_bytes());
+        repr[16..].copy_from_slice(&hi.to_le_bytes());
+        Fr::from_repr(repr)
+    }
+
+    /// Splits the canonical integer value of `self` into its low and high
+    /// 128-bit halves, inverting `pack_u128_pair`.
+    pub fn unpack_u128_pair(&self) -> (u128, u128) {
+        let repr = self.to_repr();
+        (
+            u128::from_le_bytes(repr[..16].try_into().unwrap()),
+            u128::from_le_bytes(repr[16..].try_into().unwrap()),
+        )
+    }
+
+    /// Interprets `limbs` as a little-endian canonical integer, like
+    /// `from_raw`, but returns `None` instead of accepting `limbs >= MODULUS`.
+    pub fn checked_from_raw(limbs: [u64; 4]) -> Option<Fr> {
+        let modulus = Fr::modulus_limbs();
+        limbs
+            .iter()
+            .rev()
+            .lt(modulus.iter().rev())
+            .then(|| Fr::from_raw(limbs))
+    }
+
+    /// Interprets `bytes` as a little-endian integer of any length and reduces
+    /// it modulo the field modulus. Never fails; an empty slice is zero. This
+    /// matches arkworks' `PrimeField::from_le_bytes_mod_order`.
+    pub fn from_le_bytes_mod_order(bytes: &[u8]) -> Fr {
+        let digits: Vec<u64> = bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut buf = [0u8; 8];
+                buf[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(buf)
+            })
+            .collect();
+        Fr::from_u64_digits(&digits)
+    }
+
+    /// Returns whether `self` and `other` are the same residue, reducing both
+    /// internal representations before comparing so that non-canonical limbs
+    /// cannot cause a false negative. `ct_eq` remains the fast path for
+    /// canonical values. Runs in constant time.
+    pub fn ct_eq_reduced(&self, other: &Fr) -> Choice {
+        let mut pair = [*self, *other];
+        Fr::batch_normalize(&mut pair);
+        (pair[0] - pair[1]).is_zero()
+    }
+
+    /// Evaluates the polynomial with coefficients `coeffs` (lowest degree
+    /// first) at every point of `points` using Horner's rule.
+    pub fn eval_poly_multi(coeffs: &[Fr], points: &[Fr]) -> Vec<Fr> {
+        points.iter().map(|x| horner(coeffs, x)).collect()
+    }
+
+    /// Parallel version of `eval_poly_multi`, evaluating the points across
+    /// the rayon thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_eval_poly_multi(coeffs: &[Fr], points: &[Fr]) -> Vec<Fr> {
+        use rayon::prelude::*;
+
+        points.par_iter().map(|x| horner(coeffs, x)).collect()
+    }
+
+    /// Returns the canonical big-endian encoding of `self` with leading zero
+    /// bytes removed, as EVM calldata encodes integers compactly. Zero
+    /// encodes as the empty string rather than a single zero byte, so every
+    /// encoding is the unique shortest one. Variable length, and therefore
+    /// not constant time.
+    pub fn to_bytes_be_trimmed(&self) -> Vec<u8> {
+        let repr = self.to_repr();
+        let len = repr.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        repr[..len].iter().rev().copied().collect()
+    }
+
+    /// Parses a big-endian encoding of at most 32 bytes, left-padding it with
+    /// zeros. Accepts the output of `to_bytes_be_trimmed` as well as padded
+    /// forms such as a single zero byte. Returns none if `bytes` is longer
+    /// than 32 bytes or encodes an integer not less than the modulus.
+    pub fn from_bytes_be_padded(bytes: &[u8]) -> CtOption<Fr> {
+        if bytes.len() > 32 {
+            return CtOption::new(Fr::ZERO, Choice::from(0));
+        }
+        let mut repr = [0u8; 32];
+        for (dst, src) in repr.iter_mut().zip(bytes.iter().rev()) {
+            *dst = *src;
+        }
+        Fr::from_repr(repr)
+    }
+
+    /// Returns `self^{-1}` if `invert` is set and `self` otherwise, computing
+    /// both and selecting so that `invert` stays secret. Returns none only if
+    /// `invert` is set and `self` is zero.
+    pub fn conditional_inverse(&self, invert: Choice) -> CtOption<Fr> {
+        let inv = self.invert();
+        let value = Fr::conditional_select(self, &inv.unwrap_or(Fr::ZERO), invert);
+        CtOption::new(value, !invert | inv.is_some())
+    }
+
+    /// Samples `n` independent uniformly random elements.
+    pub fn random_vec(mut rng: impl RngCore, n: usize) -> Vec<Fr> {
+        (0..n).map(|_| Fr::random(&mut rng)).collect()
+    }
+
+    /// Overwrites every element of `out` with an independent uniformly random
+    /// element.
+    pub fn fill_random(mut rng: impl RngCore, out: &mut [Fr]) {
+        for x in out.iter_mut() {
+            *x = Fr::random(&mut rng);
+        }
+    }
+
+    /// Returns whether `candidate` is a square root of `self`, i.e. whether
+    /// `candidate^2 == self`. Checking a root supplied as nondeterministic
+    /// advice costs one squaring, far less than computing it. Either root is
+    /// accepted. Runs in constant time.
+    pub fn sqrt_verify(&self, candidate: &Fr) -> Choice {
+        candidate.square().ct_eq(self)
+    }
+
+    /// Returns the canonical integer value of `self` if it fits in a `u64`,
+    /// i.e. if its upper three limbs are zero.
+    pub fn try_into_u64(&self) -> Option<u64> {
+        let repr = self.to_repr();
+        repr[8..]
+            .iter()
+            .all(|&b| b == 0)
+            .then(|| u64::from_le_bytes(repr[..8].try_into().unwrap()))
+    }
+
+    /// Returns the low 64 bits of the canonical integer value of `self`,
+    /// discarding the rest.
+    pub fn as_u64_lossy(&self) -> u64 {
+        u64::from_le_bytes(self.to_repr()[..8].try_into().unwrap())
+    }
+
+    /// Accumulates `c * x` into `self`, the inner step of streaming linear
+    /// combinations. This is [`Fr::mul_add`], so on the host the product and
+    /// the accumulator share a single reduction.
+    pub fn add_scaled(&mut self, c: &Fr, x: &Fr) {
+        *self = c.mul_add(x, self);
+    }
+
+    /// Returns `self / 2`. Both backends multiply by `TWO_INV`: a single
+    /// Montgomery multiplication on the host, a single syscall in the zkVM,
+    /// and constant time either way, unlike branching on the parity of the
+    /// canonical value.
+    pub fn halve(&self) -> Fr {
+        self * &<Fr as PrimeField>::TWO_INV
+    }
+
+    /// Reduces a 64-byte little-endian integer modulo the field modulus. This
+    /// is the byte order of `FromUniformBytes::from_uniform_bytes` and of
+    /// halo2/zcash-style transcripts.
+    pub fn from_bytes_le_wide(bytes: &[u8; 64]) -> Fr {
+        let mut digits = [0u64; 8];
+        for (digit, chunk) in digits.iter_mut().zip(bytes.chunks_exact(8)) {
+            *digit = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Fr::from_u64_digits(&digits)
+    }
+
+    /// Reduces a 64-byte big-endian integer modulo the field modulus.
+    ///
+    /// Ethereum-side Fiat–Shamir transcripts (Solidity verifiers, `keccak256`
+    /// challenges read as `uint256`) treat hash output as big-endian, so
+    /// concatenated 64-byte digests from that world should go through this
+    /// function rather than [`Fr::from_bytes_le_wide`].
+    pub fn from_bytes_be_wide(bytes: &[u8; 64]) -> Fr {
+        let mut digits = [0u64; 8];
+        for (digit, chunk) in digits.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+            *digit = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        Fr::from_u64_digits(&digits)
+    }
+
+    /// Writes `a[i] + b[i]` to `out[i]` for every `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a`, `b` and `out` do not all have the same length.
+    pub fn batch_add(a: &[Fr], b: &[Fr], out: &mut [Fr]) {
+        assert_eq!(a.len(), b.len(), "length mismatch");
+        assert_eq!(a.len(), out.len(), "length mismatch");
+        for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+            *o = x + y;
+        }
+    }
+
+    /// Writes `a[i] - b[i]` to `out[i]` for every `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a`, `b` and `out` do not all have the same length.
+    pub fn batch_sub(a: &[Fr], b: &[Fr], out: &mut [Fr]) {
+        assert_eq!(a.len(), b.len(), "length mismatch");
+        assert_eq!(a.len(), out.len(), "length mismatch");
+        for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+            *o = x - y;
+        }
+    }
+
+    /// Parallel version of `batch_add`, splitting the slices across the rayon
+    /// thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_batch_add(a: &[Fr], b: &[Fr], out: &mut [Fr]) {
+        use rayon::prelude::*;
+
+        assert_eq!(a.len(), b.len(), "length mismatch");
+        assert_eq!(a.len(), out.len(), "length mismatch");
+        out.par_iter_mut()
+            .zip(a.par_iter().zip(b.par_iter()))
+            .for_each(|(o, (x, y))| *o = x + y);
+    }
+
+    /// Parallel version of `batch_sub`, splitting the slices across the rayon
+    /// thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_batch_sub(a: &[Fr], b: &[Fr], out: &mut [Fr]) {
+        use rayon::prelude::*;
+
+        assert_eq!(a.len(), b.len(), "length mismatch");
+        assert_eq!(a.len(), out.len(), "length mismatch");
+        out.par_iter_mut()
+            .zip(a.par_iter().zip(b.par_iter()))
+            .for_each(|(o, (x, y))| *o = x - y);
+    }
+
+    /// Returns whether `self` lies in the multiplicative subgroup of order
+    /// `2^k`, i.e. whether `self^(2^k) == 1`. For `k > S` this is the same
+    /// subgroup as for `k = S`, the largest power of two dividing
+    /// `MODULUS - 1`. Constant time in `self`, variable time in `k`.
+    pub fn in_subgroup_of_order_2k(&self, k: u32) -> Choice {
+        self.pow_2k(k).ct_eq(&Fr::ONE)
+    }
+
+    /// Deterministically samples a uniform field element from `seed`, by
+    /// seeding ChaCha20 and rejection sampling 254-bit candidates until one is
+    /// below the modulus. The same seed always gives the same element.
+    #[cfg(feature = "rand-seed")]
+    pub fn random_from_seed(seed: [u8; 32]) -> Fr {
+        use rand_core::SeedableRng;
+
+        let mut rng = rand_chacha::ChaCha20Rng::from_seed(seed);
+        let mut bytes = [0u8; 32];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            // Clear the top two bits: NUM_BITS is 254.
+            bytes[31] &= 0x3f;
+            if let Some(x) = Option::from(Fr::from_repr(bytes)) {
+                return x;
+            }
+        }
+    }
+
+    /// Size in bytes of the canonical encoding `Repr`.
+    pub const NUM_BYTES: usize = 32;
+
+    /// Returns the order of the additive group, i.e. the field modulus, as a
+    /// 32-byte big-endian integer, the layout Solidity verifiers embed as a
+    /// `uint256` constant. Same as [`Fr::modulus_bytes_be`].
+    pub fn order_bytes_be() -> [u8; Fr::NUM_BYTES] {
+        Fr::modulus_bytes_be()
+    }
+
+    /// Like [`Fr::batch_invert`], but also returns one flag per element that
+    /// is set iff that element was nonzero and therefore inverted. Runs in
+    /// constant time.
+    pub fn batch_invert_flags(elements: &mut [Fr]) -> Vec<Choice> {
+        let flags = elements.iter().map(|x| !x.is_zero()).collect();
+        Fr::batch_invert(elements);
+        flags
+    }
+
+    /// Formats the canonical integer value of `self` in decimal, for test
+    /// output and debugging. Variable time.
+    pub fn debug_decimal(&self) -> String {
+        use core::fmt::Write;
+
+        // Base 10^19 digits, least significant first.
+        const BASE: u128 = 10_000_000_000_000_000_000;
+        let mut limbs: [u64; 4] = (*self).into();
+        let mut digits = Vec::new();
+        while limbs != [0; 4] {
+            let mut rem = 0u128;
+            for limb in limbs.iter_mut().rev() {
+                let cur = (rem << 64) | *limb as u128;
+                *limb = (cur / BASE) as u64;
+                rem = cur % BASE;
+            }
+            digits.push(rem as u64);
+        }
+
+        let Some((top, rest)) = digits.split_last() else {
+            return "0".to_string();
+        };
+        let mut out = top.to_string();
+        for digit in rest.iter().rev() {
+            write!(out, "{:019}", digit).unwrap();
+        }
+        out
+    }
+
+    /// Negates `self` in place.
+    pub fn neg_assign(&mut self) {
+        *self = -&*self;
+    }
+
+    /// Raises `self` to the little-endian exponent `exp` with a Montgomery
+    /// ladder.
+    ///
+    /// Every one of the 256 exponent bits costs exactly one multiplication
+    /// and one squaring on the same two registers, with the bit only deciding
+    /// a constant-time swap. Unlike [`Fr::pow_ct`], there is no table whose
+    /// selected entry could leak through the memory access pattern, so the
+    /// sequence of operations and accesses is the same for every exponent.
+    /// This targets simple and differential power analysis on secret
+    /// exponents; it does not help against fault injection.
+    pub fn pow_ladder(&self, exp: &[u64; 4]) -> Fr {
+        // Invariant: r1 = r0 * self
+        let mut r0 = Fr::ONE;
+        let mut r1 = *self;
+        for limb in exp.iter().rev() {
+            for i in (0..64).rev() {
+                let bit = Choice::from(((limb >> i) & 1) as u8);
+                Fr::conditional_swap(&mut r0, &mut r1, bit);
+                r1 *= r0;
+                r0 = r0.square();
+                Fr::conditional_swap(&mut r0, &mut r1, bit);
+            }
+        }
+        r0
+    }
+
+    /// Interprets `limbs` as a little-endian canonical integer and rejects
+    /// anything not less than the modulus, reporting whether the input was the
+    /// modulus itself or above it. Meant for validating external input, so it
+    /// runs in variable time.
+    pub fn from_u256_limbs_checked(limbs: [u64; 4]) -> Result<Fr, Boundary> {
+        let modulus = Fr::modulus_limbs();
+        match limbs.iter().rev().cmp(modulus.iter().rev()) {
+            core::cmp::Ordering::Less => Ok(Fr::from_raw(limbs)),
+            core::cmp::Ordering::Equal => Err(Boundary::EqualsModulus),
+            core::cmp::Ordering::Greater => Err(Boundary::AboveModulus),
+        }
+    }
+
+    /// Swaps `a` and `b` if `choice` is set and leaves them alone otherwise,
+    /// in constant time. The building block of constant-time sorting
+    /// networks.
+    pub fn conditional_swap(a: &mut Fr, b: &mut Fr, choice: Choice) {
+        let new_a = Fr::conditional_select(a, b, choice);
+        *b = Fr::conditional_select(b, a, choice);
+        *a = new_a;
+    }
+
+    /// Returns `self / 2^k`, the inverse of doubling `k` times, as one
+    /// multiplication by [`Fr::inv_2k`]. The running time depends only on
+    /// `k`.
+    pub fn div_2k(&self, k: u32) -> Fr {
+        self * Fr::inv_2k(k)
+    }
+
+    /// Compares the canonical integer values of `self` and `other`.
+    ///
+    /// This returns as soon as the first differing byte is found, so its
+    /// running time leaks how long the common prefix is. Only use it on public
+    /// values, e.g. for sorting or map keys.
+    pub fn canonical_cmp(&self, other: &Fr) -> core::cmp::Ordering {
+        self.to_repr()
+            .iter()
+            .rev()
+            .cmp(other.to_repr().iter().rev())
+    }
+
+    /// Evaluates at `at` the unique polynomial of degree less than
+    /// `points.len()` passing through every `(x_i, y_i)` in `points`, without
+    /// computing its coefficients:
+    ///
+    /// `Σ y_i * Π_{j != i} (at - x_j) / (x_i - x_j)`
+    ///
+    /// The denominators are inverted together with [`Fr::batch_invert`].
+    /// Returns none if two `x_i` coincide. No points interpolate the zero
+    /// polynomial. Costs `O(n^2)` multiplications.
+    pub fn lagrange_eval(points: &[(Fr, Fr)], at: &Fr) -> CtOption<Fr> {
+        let mut denominators: Vec<Fr> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (xi, _))| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, (xj, _))| xi - xj)
+                    .product()
+            })
+            .collect();
+        let distinct = denominators
+            .iter()
+            .fold(Choice::from(1), |acc, d| acc & !d.is_zero());
+        Fr::batch_invert(&mut denominators);
+
+        let value = points
+            .iter()
+            .zip(&denominators)
+            .enumerate()
+            .map(|(i, ((_, yi), inv))| {
+                let numerator: Fr = points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, (xj, _))| at - xj)
+                    .product();
+                yi * numerator * inv
+            })
+            .sum();
+        CtOption::new(value, distinct)
+    }
+
+    /// Returns the canonical encoding of `-self`, computed directly as the
+    /// integer `MODULUS - self` (and `0` for zero) without building the
+    /// negated element. Runs in constant time.
+    pub fn neg_to_repr(&self) -> [u8; 32] {
+        let repr = self.to_repr();
+        let is_zero = self.is_zero();
+        let mut out = [0u8; 32];
+        let mut borrow = 0u128;
+        for ((out, x), m) in out
+            .chunks_exact_mut(8)
+            .zip(repr.chunks_exact(8))
+            .zip(Fr::modulus_limbs())
+        {
+            let x = u64::from_le_bytes(x.try_into().unwrap());
+            let diff = (m as u128).wrapping_sub(x as u128 + borrow);
+            borrow = diff >> 127;
+            let limb = u64::conditional_select(&(diff as u64), &0, is_zero);
+            out.copy_from_slice(&limb.to_le_bytes());
+        }
+        out
+    }
+
+    /// Subtracts `rhs` from `self` and returns whether the integer
+    /// subtraction of the canonical values underflowed, i.e. whether the
+    /// result wrapped past zero and had the modulus added back. Meant for
+    /// debugging constraint systems; variable time.
+    pub fn checked_sub_assign(&mut self, rhs: &Fr) -> bool {
+        let wrapped = self.canonical_cmp(rhs) == core::cmp::Ordering::Less;
+        *self -= rhs;
+        wrapped
+    }
+
+    /// Samples a uniform field element from operating-system entropy via
+    /// `getrandom`, rejection sampling 254-bit candidates until one is below
+    /// the modulus. A convenience for one-off keys and nonces; pass an RNG to
+    /// `Field::random` in hot paths. Not available in the zkVM, which has no
+    /// operating-system entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system cannot provide entropy.
+    #[cfg(all(
+        feature = "entropy",
+        not(all(target_os = "zkvm", target_vendor = "succinct"))
+    ))]
+    pub fn from_entropy() -> Fr {
+        let mut bytes = [0u8; 32];
+        loop {
+            getrandom::getrandom(&mut bytes).expect("failed to read OS entropy");
+            // Clear the top two bits: NUM_BITS is 254.
+            bytes[31] &= 0x3f;
+            if let Some(x) = Option::from(Fr::from_repr(bytes)) {
+                return x;
+            }
+        }
+    }
+
+    /// Returns `[1, base, base^2, ..., base^(n - 1)]`.
+    pub fn powers(base: &Fr, n: usize) -> Vec<Fr> {
+        let mut out = vec![Fr::ZERO; n];
+        Fr::powers_into(base, &mut out);
+        out
+    }
+
+    /// Fills `out` with `[1, base, base^2, ...]`, as many powers as it has
+    /// room for, without allocating.
+    pub fn powers_into(base: &Fr, out: &mut [Fr]) {
+        let mut power = Fr::ONE;
+        for entry in out.iter_mut() {
+            *entry = power;
+            power *= base;
+        }
+    }
+
+    /// Returns `(2^k)^-1`, e.g. the `1/n` that normalizes an inverse FFT of
+    /// size `n = 2^k`. Computed as `TWO_INV^k` by square-and-multiply rather
+    /// than a general inversion; variable time in `k`.
+    pub fn inv_2k(k: u32) -> Fr {
+        <Fr as PrimeField>::TWO_INV.pow_vartime_u64(k as u64)
+    }
+}
+
+/// Reduces a 256-bit integer modulo the scalar field modulus, so values at or
+/// above the modulus wrap around exactly like EVM `addmod`/`mulmod` results.
+#[cfg(feature = "ruint")]
+impl From<ruint::aliases::U256> for Fr {
+    fn from(value: ruint::aliases::U256) -> Fr {
+        Fr::from_u64_digits(value.as_limbs())
+    }
+}
+
+/// Returns the canonical integer value of the field element.
+#[cfg(feature = "ruint")]
+impl From<Fr> for ruint::aliases::U256 {
+    fn from(value: Fr) -> ruint::aliases::U256 {
+        ruint::aliases::U256::from_le_bytes(value.to_repr())
+    }
+}
+
+/// Returns the canonical integer value as little-endian limbs, the inverse of
+/// [`Fr::from_raw`] on canonical input.
+impl From<Fr> for [u64; 4] {
+    fn from(value: Fr) -> [u64; 4] {
+        let repr = value.to_repr();
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(repr.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        limbs
+    }
+}
+
+impl From<u128> for Fr {
+    fn from(value: u128) -> Fr {
+        Fr::from_u128(value)
+    }
+}
+
+/// Multiplying or adding a `u64` treats it as the field element
+/// `Fr::from(k)`, so `x * 3u64 == x * Fr::from(3u64)`. Unlike
+/// [`Fr::mul_by_u64`], the multiplication does not branch on `k`.
+impl MulAssign<&u64> for Fr {
+    fn mul_assign(&mut self, rhs: &u64) {
+        *self *= Fr::from(*rhs);
+    }
+}
+
+impl MulAssign<u64> for Fr {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self *= &rhs;
+    }
+}
+
+impl AddAssign<&u64> for Fr {
+    fn add_assign(&mut self, rhs: &u64) {
+        *self += Fr::from(*rhs);
+    }
+}
+
+impl AddAssign<u64> for Fr {
+    fn add_assign(&mut self, rhs: u64) {
+        *self += &rhs;
+    }
+}
+
+crate::impl_binops_multiplicative_mixed!(Fr, u64, Fr);
+crate::impl_add_binop_specify_output!(Fr, u64, Fr);
+
+/// Field elements encode as the 32-byte little-endian canonical integer in both
+/// modes, which is byte-for-byte what arkworks writes for its BN254 scalar.
+#[cfg(feature = "ark-serialize")]
+impl ark_serialize::CanonicalSerialize for Fr {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        _compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        writer.write_all(&self.to_repr())?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: ark_serialize::Compress) -> usize {
+        32
+    }
+}
+
+/// Every decoded `Fr` is already reduced, so there is nothing left to check.
+#[cfg(feature = "ark-serialize")]
+impl ark_serialize::Valid for Fr {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
+/// Non-canonical encodings are rejected regardless of `validate`, matching
+/// arkworks' own field deserialization.
+#[cfg(feature = "ark-serialize")]
+impl ark_serialize::CanonicalDeserialize for Fr {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        _compress: ark_serialize::Compress,
+        _validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let mut repr = [0u8; 32];
+        reader.read_exact(&mut repr)?;
+        Option::from(Fr::from_repr(repr)).ok_or(ark_serialize::SerializationError::InvalidData)
+    }
+}
+
+/// Remainder in the field sense: every nonzero element divides every other, so
+/// `a % b` is `ZERO` for any nonzero `b`, exactly as `a == (a / b) * b + 0`.
+/// Like integer `%`, a zero divisor panics. This is deliberately *not* the
+/// remainder of the canonical integers; convert with `to_repr` for that.
+///
+/// Leaving `%` unimplemented produced an opaque trait error for people
+/// expecting modular reduction, and deprecating a trait impl has no effect,
+/// so defining the operator with its only field-consistent meaning is the
+/// least surprising choice.
+impl<'a, 'b> Rem<&'b Fr> for &'a Fr {
+    type Output = Fr;
+
+    fn rem(self, rhs: &'b Fr) -> Fr {
+        assert!(
+            !bool::from(rhs.is_zero()),
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        Fr::ZERO
+    }
+}
+
+impl Rem<Fr> for Fr {
+    type Output = Fr;
+
+    fn rem(self, rhs: Fr) -> Fr {
+        &self % &rhs
+    }
+}
+
+/// The `rkyv` archived form of [`Fr`]: the canonical integer value as four
+/// little-endian limbs, independent of the backend's internal representation.
+#[cfg(feature = "rkyv")]
+#[derive(Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ArchivedFr(pub [rkyv::Archived<u64>; 4]);
+
+#[cfg(feature = "rkyv")]
+impl ArchivedFr {
+    /// Returns the archived limbs, least significant first.
+    pub fn limbs(&self) -> [u64; 4] {
+        [0, 1, 2, 3].map(|i| u64::from(self.0[i]))
+    }
+
+    /// Converts to `Fr`, reducing limbs that are not less than the modulus,
+    /// which only a corrupted or foreign archive can contain. This is what
+    /// `Deserialize` does.
+    pub fn to_fr(&self) -> Fr {
+        Fr::from_u64_digits(&self.limbs())
+    }
+
+    /// Converts to `Fr`, returning `None` if the limbs are not less than the
+    /// modulus. Use this on archives from untrusted sources.
+    pub fn to_fr_checked(&self) -> Option<Fr> {
+        Fr::checked_from_raw(self.limbs())
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl rkyv::Archive for Fr {
+    type Archived = ArchivedFr;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, _: (), out: *mut ArchivedFr) {
+        let limbs: [u64; 4] = (*self).into();
+        // `ArchivedFr` is a transparent wrapper around the archived array.
+        <[u64; 4] as rkyv::Archive>::resolve(&limbs, pos, [(); 4], out.cast());
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<S: rkyv::Fallible + ?Sized> rkyv::Serialize<S> for Fr {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<D: rkyv::Fallible + ?Sized> rkyv::Deserialize<Fr, D> for ArchivedFr {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Fr, D::Error> {
+        Ok(self.to_fr())
+    }
+}
+
+/// `Fr` is a transparent wrapper around `[u64; 4]`, so every bit pattern is a
+/// valid value as far as memory safety goes. Casting reinterprets the
+/// backend's internal limbs as-is: Montgomery form on the host, canonical
+/// limbs in the zkVM, in native byte order. The bytes are therefore not a
+/// portable encoding, and nothing checks that limbs cast from bytes are below
+/// the modulus; use `to_repr`/`from_repr` for data crossing a trust or
+/// platform boundary.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Fr {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Fr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_to_bits_le() {
+        assert!(Fr::ZERO.to_bits_le().iter().all(|b| !b));
+
+        let bits = Fr::ONE.to_bits_le();
+        assert!(bits[0]);
+        assert!(bits[1..].iter().all(|b| !b));
+
+        let bits = Fr::from(0x8000_0000_0000_0005u64).to_bits_le();
+        assert!(bits[0]);
+        assert!(!bits[1]);
+        assert!(bits[2]);
+        assert!(bits[63]);
+        assert!(!bits[64]);
+
+        // MODULUS - 1 = 0x30644e72...43e1f593f0000000
+        let bits = (-Fr::ONE).to_bits_le();
+        assert!(bits[..28].iter().all(|b| !b));
+        assert!(bits[28] && bits[29] && bits[30] && bits[31]);
+        assert!(!bits[255]);
+        assert!(!bits[254]);
+        assert!(bits[253]);
+        assert!(bits[252]);
+        assert!(!bits[251]);
+    }
+
+    #[test]
+    fn test_conditional_assign() {
+        let a = Fr::from(3u64);
+        let b = Fr::from(5u64);
+
+        let mut x = a;
+        x.conditional_assign(&b, Choice::from(0));
+        assert_eq!(x, a);
+
+        x.conditional_assign(&b, Choice::from(1));
+        assert_eq!(x, b);
+    }
+
+    #[test]
+    fn test_tree_sum() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let elements: Vec<Fr> = (0..1000).map(|_| Fr::random(&mut rng)).collect();
+
+        for n in [0, 1, 2, 3, 7, 64, 1000] {
+            let expected: Fr = elements[..n].iter().sum();
+            assert_eq!(Fr::tree_sum(&elements[..n]), expected);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_tree_sum() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let elements: Vec<Fr> = (0..10_000).map(|_| Fr::random(&mut rng)).collect();
+
+        let expected: Fr = elements.iter().sum();
+        assert_eq!(Fr::par_tree_sum(&elements), expected);
+        assert_eq!(
+            Fr::par_tree_sum(&elements[..3]),
+            Fr::tree_sum(&elements[..3])
+        );
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        let coeffs = [Fr::from(2u64), Fr::from(3u64), Fr::from(5u64)];
+        let points = [Fr::from(7u64), Fr::from(11u64), Fr::from(13u64)];
+        assert_eq!(
+            Fr::linear_combination(&coeffs, &points),
+            Fr::from(2 * 7 + 3 * 11 + 5 * 13u64)
+        );
+        assert_eq!(Fr::linear_combination(&[], &[]), Fr::ZERO);
+
+        // Lengths on both sides of the reduction chunk, with random and
+        // extreme terms.
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for n in [1, 7, 8, 9, 16, 17, 100] {
+            let coeffs = Fr::random_vec(&mut rng, n);
+            let points = Fr::random_vec(&mut rng, n);
+            let expected = coeffs
+                .iter()
+                .zip(&points)
+                .fold(Fr::ZERO, |acc, (c, x)| acc + c * x);
+            assert_eq!(Fr::linear_combination(&coeffs, &points), expected);
+
+            let minus_one = vec![-Fr::ONE; n];
+            assert_eq!(
+                Fr::linear_combination(&minus_one, &minus_one),
+                Fr::from(n as u64)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_linear_combination_length_mismatch() {
+        Fr::linear_combination(&[Fr::ONE, Fr::ONE], &[Fr::ONE]);
+    }
+
+    #[test]
+    fn test_from_le_bits() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let x = Fr::random(&mut rng);
+            assert_eq!(Fr::from_le_bits(&x.to_bits_le()), x);
+        }
+
+        assert_eq!(Fr::from_le_bits(&[]), Fr::ZERO);
+        assert_eq!(Fr::from_le_bits(&[true]), Fr::ONE);
+        assert_eq!(Fr::from_le_bits(&[false, true, true]), Fr::from(6u64));
+
+        // MODULUS - 1 is even, so setting its lowest bit encodes MODULUS itself.
+        let mut bits = (-Fr::ONE).to_bits_le();
+        assert!(!bits[0]);
+        bits[0] = true;
+        assert_eq!(Fr::from_le_bits(&bits), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_mul_by_u64() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let x = Fr::random(&mut rng);
+        for k in (0..=40).chain([255, 1 << 32, u64::MAX]) {
+            assert_eq!(x.mul_by_u64(k), x * Fr::from(k));
+        }
+    }
+
+    #[test]
+    fn test_batch_invert_in() {
+        use ff::BatchInvert;
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut elements: Vec<Fr> = (0..100).map(|_| Fr::random(&mut rng)).collect();
+        let mut expected = elements.clone();
+        expected.iter_mut().batch_invert();
+
+        let mut scratch = vec![Fr::ZERO; elements.len()];
+        assert!(bool::from(
+            Fr::batch_invert_in(&mut elements, &mut scratch).is_some()
+        ));
+        assert_eq!(elements, expected);
+
+        assert!(bool::from(Fr::batch_invert_in(&mut [], &mut []).is_some()));
+
+        let mut elements = [Fr::from(2u64), Fr::ZERO, Fr::from(3u64)];
+        let mut scratch = [Fr::ZERO; 3];
+        assert!(bool::from(
+            Fr::batch_invert_in(&mut elements, &mut scratch).is_none()
+        ));
+        assert_eq!(elements, [Fr::from(2u64), Fr::ZERO, Fr::from(3u64)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_batch_invert_in_length_mismatch() {
+        let _ = Fr::batch_invert_in(&mut [Fr::ONE; 2], &mut [Fr::ZERO; 1]);
+    }
+
+    #[cfg(feature = "bits")]
+    #[test]
+    fn test_prime_field_bits() {
+        fn le_bits<F: ff::PrimeFieldBits>(x: &F) -> Vec<bool> {
+            x.to_le_bits().iter().by_vals().collect()
+        }
+
+        fn char_bits<F: ff::PrimeFieldBits>() -> Vec<bool> {
+            F::char_le_bits().iter().by_vals().collect()
+        }
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..10 {
+            let x = Fr::random(&mut rng);
+            assert_eq!(le_bits(&x), x.to_bits_le());
+        }
+
+        let mut modulus = (-Fr::ONE).to_bits_le();
+        modulus[0] = true;
+        assert_eq!(char_bits::<Fr>(), modulus);
+    }
+
+    #[test]
+    fn test_to_wnaf() {
+        fn recompose(digits: &[i8]) -> Fr {
+            digits.iter().rev().fold(Fr::ZERO, |acc, &d| {
+                let d_abs = Fr::from(d.unsigned_abs() as u64);
+                acc.double() + if d < 0 { -d_abs } else { d_abs }
+            })
+        }
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut scalars: Vec<Fr> = (0..20).map(|_| Fr::random(&mut rng)).collect();
+        scalars.extend([Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::from(u64::MAX)]);
+
+        for window in 2..=8 {
+            for x in scalars.iter() {
+                let digits = x.to_wnaf(window);
+                assert_eq!(recompose(&digits), *x);
+                for (i, &d) in digits.iter().enumerate() {
+                    if d != 0 {
+                        assert_ne!(d % 2, 0);
+                        assert!((d.unsigned_abs() as i64) < 1 << (window - 1));
+                        assert!(digits[i + 1..].iter().take(window - 1).all(|&d| d == 0));
+                    }
+                }
+            }
+        }
+        assert!(Fr::ZERO.to_wnaf(4).is_empty());
+    }
+
+    #[test]
+    fn test_pow_half_order() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let x = Fr::random(&mut rng);
+            let e = x.pow_half_order();
+            assert!(e == Fr::ONE || e == -Fr::ONE);
+            assert_eq!(x.square().pow_half_order(), Fr::ONE);
+        }
+
+        assert_eq!(Fr::ZERO.pow_half_order(), Fr::ZERO);
+        assert_eq!(Fr::from(4u64).pow_half_order(), Fr::ONE);
+        assert_eq!(Fr::MULTIPLICATIVE_GENERATOR.pow_half_order(), -Fr::ONE);
+    }
+
+    fn modulus_biguint() -> BigUint {
+        BigUint::parse_bytes(
+            b"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn biguint_to_fr(x: &BigUint) -> Fr {
+        let mut repr = [0u8; 32];
+        let bytes = (x % modulus_biguint()).to_bytes_le();
+        repr[..bytes.len()].copy_from_slice(&bytes);
+        Fr::from_repr(repr).unwrap()
+    }
+
+    #[test]
+    fn test_from_u64_digits() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        assert_eq!(Fr::from_u64_digits(&[]), Fr::ZERO);
+
+        for len in [1, 4, 8, 13] {
+            let digits: Vec<u64> = (0..len).map(|_| rng.next_u64()).collect();
+            assert_eq!(
+                Fr::from_u64_digits(&digits),
+                biguint_to_fr(&BigUint::from_slice(
+                    &digits
+                        .iter()
+                        .flat_map(|d| [*d as u32, (*d >> 32) as u32])
+                        .collect::<Vec<_>>()
+                ))
+            );
+        }
+
+        assert_eq!(
+            Fr::from_u64_digits(&[u64::MAX; 4]),
+            biguint_to_fr(&((BigUint::from(1u8) << 256) - 1u8))
+        );
+    }
+
+    #[test]
+    fn test_additive_operator_forms() {
+        let a = Fr::from(7u64);
+        let b = Fr::from(5u64);
+
+        assert_eq!(a + b, Fr::from(12u64));
+        assert_eq!(a + &b, Fr::from(12u64));
+        assert_eq!(&a + b, Fr::from(12u64));
+        assert_eq!(&a + &b, Fr::from(12u64));
+        assert_eq!(a - b, Fr::from(2u64));
+        assert_eq!(a - &b, Fr::from(2u64));
+        assert_eq!(&a - b, Fr::from(2u64));
+        assert_eq!(&a - &b, Fr::from(2u64));
+
+        let mut x = a;
+        x += b;
+        x += &b;
+        assert_eq!(x, Fr::from(17u64));
+        x -= b;
+        x -= &b;
+        assert_eq!(x, a);
+    }
+
+    #[test]
+    fn test_modulus_accessors() {
+        let modulus = match Fr::MODULUS.strip_prefix("0x") {
+            Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+            None => BigUint::parse_bytes(Fr::MODULUS.as_bytes(), 10),
+        }
+        .unwrap();
+        assert_eq!(modulus, modulus_biguint());
+        assert_eq!(
+            BigUint::from_bytes_be(&Fr::modulus_bytes_be()),
+            modulus_biguint()
+        );
+
+        let mut limbs = Fr::modulus_limbs();
+        assert_eq!(limbs[0] & 1, 1);
+        limbs[0] -= 1;
+        let minus_one = Fr::from_str_vartime(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616",
+        )
+        .unwrap();
+        assert_eq!(minus_one, -Fr::ONE);
+        assert_eq!(minus_one.to_repr(), Fr::from_raw(limbs).to_repr());
+    }
+
+    #[test]
+    fn test_conditional_select_slice() {
+        let a = [Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)];
+        let b = [Fr::from(4u64), Fr::from(5u64), Fr::from(6u64)];
+        let mut out = [Fr::ZERO; 3];
+
+        Fr::conditional_select_slice(&a, &b, &mut out, Choice::from(1));
+        assert_eq!(out, a);
+
+        Fr::conditional_select_slice(&a, &b, &mut out, Choice::from(0));
+        assert_eq!(out, b);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_conditional_select_slice_length_mismatch() {
+        Fr::conditional_select_slice(
+            &[Fr::ONE; 2],
+            &[Fr::ONE; 2],
+            &mut [Fr::ZERO; 3],
+            Choice::from(1),
+        );
+    }
+
+    #[cfg(feature = "ruint")]
+    #[test]
+    fn test_ruint_conversions() {
+        use ruint::aliases::U256;
+
+        let modulus = U256::from_limbs(Fr::modulus_limbs());
+
+        let below = modulus - U256::from(1u64);
+        assert_eq!(Fr::from(below), -Fr::ONE);
+        assert_eq!(U256::from(Fr::from(below)), below);
+
+        assert_eq!(Fr::from(modulus), Fr::ZERO);
+        assert_eq!(Fr::from(modulus + U256::from(5u64)), Fr::from(5u64));
+        assert_eq!(
+            U256::from(Fr::from(U256::MAX)),
+            U256::MAX.reduce_mod(modulus)
+        );
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let x = Fr::random(&mut rng);
+        assert_eq!(Fr::from(U256::from(x)), x);
+    }
+
+    #[test]
+    fn test_random_in_range() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+
+        let lo = Fr::from(10u64);
+        let hi = Fr::from(14u64);
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let x = Fr::random_in_range(&mut rng, &lo, &hi);
+            let v = x.to_repr()[0];
+            assert!(x.to_repr()[1..].iter().all(|&b| b == 0));
+            assert!((10..14).contains(&v));
+            seen[(v - 10) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let lo = Fr::from(u64::MAX);
+        let hi = -Fr::ONE;
+        for _ in 0..1000 {
+            let x = Fr::random_in_range(&mut rng, &lo, &hi);
+            assert!(!repr_lt(&x.to_repr(), &lo.to_repr()));
+            assert!(repr_lt(&x.to_repr(), &hi.to_repr()));
+        }
+
+        assert_eq!(Fr::random_in_range(&mut rng, &hi, &hi), hi);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_random_in_range_reversed() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        Fr::random_in_range(&mut rng, &Fr::from(2u64), &Fr::ONE);
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        assert_eq!(Fr::root_of_unity(0), Fr::ONE);
+        assert_eq!(Fr::root_of_unity(1), -Fr::ONE);
+        assert_eq!(Fr::root_of_unity(Fr::S), Fr::ROOT_OF_UNITY);
+
+        for k in 1..=Fr::S {
+            let root = Fr::root_of_unity(k);
+            assert_eq!(root, Fr::ROOT_OF_UNITY.pow_2k(Fr::S - k));
+            assert_eq!(root.pow_2k(k), Fr::ONE);
+            assert_ne!(root.pow_2k(k - 1), Fr::ONE);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_root_of_unity_too_large() {
+        Fr::root_of_unity(Fr::S + 1);
+    }
+
+    #[test]
+    fn test_to_radix_16() {
+        let sixteen = Fr::from(16u64);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut scalars: Vec<Fr> = (0..100).map(|_| Fr::random(&mut rng)).collect();
+        scalars.extend([Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::from(0x8888_8888u64)]);
+
+        for x in scalars {
+            let digits = x.to_radix_16();
+            assert!(digits[..63].iter().all(|d| (-8..8).contains(d)));
+            assert!((0..=4).contains(&digits[63]));
+
+            let recomposed = digits.iter().rev().fold(Fr::ZERO, |acc, &d| {
+                let d_abs = Fr::from(d.unsigned_abs() as u64);
+                acc * sixteen + if d < 0 { -d_abs } else { d_abs }
+            });
+            assert_eq!(recomposed, x);
+        }
+    }
+
+    #[cfg(feature = "ark")]
+    #[test]
+    fn test_ark_conversions() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            assert_eq!(Fr::from_ark(&a.to_ark()), a);
+            assert_eq!(Fr::from_ark(&(a.to_ark() * b.to_ark())), a * b);
+            assert_eq!(Fr::from_ark(&(a.to_ark() + b.to_ark())), a + b);
+        }
+
+        assert_eq!(Fr::ONE.to_ark(), ark_bn254::Fr::from(1u64));
+        assert_eq!(Fr::from_ark(&-ark_bn254::Fr::from(1u64)), -Fr::ONE);
+    }
+
+    #[test]
+    fn test_sqrt_vartime() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        assert_eq!(Fr::ZERO.sqrt_vartime(), Some(Fr::ZERO));
+        assert_eq!(Fr::ONE.sqrt_vartime().unwrap().square(), Fr::ONE);
+
+        for _ in 0..100 {
+            let x = Fr::random(&mut rng).square();
+            let root = x.sqrt_vartime().unwrap();
+            let ct_root = x.sqrt().unwrap();
+            assert_eq!(root.square(), x);
+            assert!(root == ct_root || root == -ct_root);
+
+            let non_residue = x * Fr::MULTIPLICATIVE_GENERATOR;
+            assert_eq!(non_residue.sqrt_vartime(), None);
+            assert!(bool::from(non_residue.sqrt().is_none()));
+        }
+    }
+
+    #[test]
+    fn test_mul_add_mul_sub() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            let c = Fr::random(&mut rng);
+            assert_eq!(a.mul_add(&b, &c), a * b + c);
+            assert_eq!(a.mul_sub(&b, &c), a * b - c);
+        }
+        assert_eq!(Fr::ZERO.mul_add(&Fr::ONE, &Fr::ONE), Fr::ONE);
+        assert_eq!(Fr::ONE.mul_sub(&Fr::ONE, &Fr::ONE), Fr::ZERO);
+        assert_eq!(Fr::ZERO.mul_sub(&Fr::ONE, &Fr::ONE), -Fr::ONE);
+
+        // Extreme operands: (-1) * (-1) + (-1) = 0 and (-1) * (-1) - (-1) = 2.
+        let m = -Fr::ONE;
+        assert_eq!(m.mul_add(&m, &m), Fr::ZERO);
+        assert_eq!(m.mul_sub(&m, &m), Fr::from(2u64));
+        assert_eq!(m.mul_add(&m, &Fr::ZERO), Fr::ONE);
+    }
+
+    #[test]
+    fn test_square_batch() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let input: Vec<Fr> = (0..33).map(|_| Fr::random(&mut rng)).collect();
+        let mut out = vec![Fr::ZERO; input.len()];
+        Fr::square_batch(&input, &mut out);
+        for (o, x) in out.iter().zip(&input) {
+            assert_eq!(*o, x * x);
+        }
+
+        Fr::square_batch(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_square_batch_length_mismatch() {
+        Fr::square_batch(&[Fr::ONE; 2], &mut [Fr::ZERO; 3]);
+    }
+
+    #[cfg(feature = "ark-serialize")]
+    #[test]
+    fn test_ark_serialize_roundtrip() {
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            let mut bytes = Vec::new();
+            a.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), a.compressed_size());
+            assert_eq!(bytes, a.to_repr());
+            assert_eq!(Fr::deserialize_compressed(&bytes[..]).unwrap(), a);
+
+            let mut uncompressed = Vec::new();
+            a.serialize_uncompressed(&mut uncompressed).unwrap();
+            assert_eq!(uncompressed, bytes);
+            assert_eq!(Fr::deserialize_uncompressed(&uncompressed[..]).unwrap(), a);
+
+            #[cfg(feature = "ark")]
+            {
+                let mut ark_bytes = Vec::new();
+                a.to_ark().serialize_compressed(&mut ark_bytes).unwrap();
+                assert_eq!(ark_bytes, bytes);
+            }
+        }
+
+        assert!(Fr::deserialize_compressed(&[0xffu8; 32][..]).is_err());
+        assert!(Fr::deserialize_uncompressed_unchecked(&[0xffu8; 32][..]).is_err());
+        assert!(Fr::deserialize_compressed(&[0u8; 31][..]).is_err());
+    }
+
+    #[test]
+    fn test_invert_flagged() {
+        let (inv, ok) = Fr::ZERO.invert_flagged();
+        assert_eq!(inv, Fr::ZERO);
+        assert!(!bool::from(ok));
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            let (inv, ok) = a.invert_flagged();
+            assert!(bool::from(ok));
+            assert_eq!(inv, a.invert().unwrap());
+            assert_eq!(a * inv, Fr::ONE);
+        }
+    }
+
+    #[test]
+    fn test_pow_vartime_u64() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let x = Fr::random(&mut rng);
+        assert_eq!(x.pow_vartime_u64(0), Fr::ONE);
+        assert_eq!(x.pow_vartime_u64(1), x);
+        assert_eq!(x.pow_vartime_u64(5), x * x * x * x * x);
+        assert_eq!(
+            x.pow_vartime_u64(u64::MAX),
+            x.pow_vartime([u64::MAX, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_shift_vartime() {
+        for k in [0u64, 1, 2, 3, 1000, u64::MAX >> 1] {
+            let x = Fr::from(k);
+            assert_eq!(x.shl_vartime(1), x.double());
+            assert_eq!(x.double().shr_vartime(1), x);
+        }
+        assert_eq!(Fr::from(7u64).shr_vartime(1), Fr::from(3u64));
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let modulus = modulus_biguint();
+        for _ in 0..20 {
+            let x = Fr::random(&mut rng);
+            let big = BigUint::from_bytes_le(&x.to_repr());
+            for n in [0u32, 1, 63, 64, 65, 130, 253, 255, 256, 300] {
+                assert_eq!(x.shr_vartime(n), biguint_to_fr(&(&big >> n as usize)));
+                assert_eq!(
+                    x.shl_vartime(n),
+                    biguint_to_fr(&((&big << n as usize) % &modulus))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_has_order_dividing() {
+        assert!(Fr::ROOT_OF_UNITY.has_order_dividing(&[1 << Fr::S]));
+        assert!(!Fr::ROOT_OF_UNITY.has_order_dividing(&[1 << (Fr::S - 1)]));
+        assert!(Fr::ROOT_OF_UNITY_INV.has_order_dividing(&[1 << Fr::S]));
+
+        let order = Fr::modulus_limbs();
+        let p_minus_1 = [order[0] - 1, order[1], order[2], order[3]];
+        assert!(Fr::MULTIPLICATIVE_GENERATOR.has_order_dividing(&p_minus_1));
+        assert!(!Fr::MULTIPLICATIVE_GENERATOR.has_order_dividing(&EXP_HALF));
+        assert!(Fr::ONE.has_order_dividing(&[1]));
+        assert!(!Fr::ZERO.has_order_dividing(&[1]));
+    }
+
+    #[test]
+    fn test_to_centered_bytes_be() {
+        let be = |x: Fr| {
+            let mut bytes = x.to_repr();
+            bytes.reverse();
+            bytes
+        };
+
+        let (sign, mag) = Fr::ZERO.to_centered_bytes_be();
+        assert!(!bool::from(sign));
+        assert_eq!(mag, [0u8; 32]);
+
+        let five = Fr::from(5u64);
+        let (sign, mag) = five.to_centered_bytes_be();
+        assert!(!bool::from(sign));
+        assert_eq!(mag, be(five));
+
+        let (sign, mag) = (-five).to_centered_bytes_be();
+        assert!(bool::from(sign));
+        assert_eq!(mag, be(five));
+
+        // (MODULUS - 1) / 2 is the largest non-negative representative, and
+        // the values just above it are the most negative ones.
+        let half = Fr::from_raw(EXP_HALF);
+        let (sign, mag) = half.to_centered_bytes_be();
+        assert!(!bool::from(sign));
+        assert_eq!(mag, be(half));
+
+        let (sign, mag) = (half + Fr::ONE).to_centered_bytes_be();
+        assert!(bool::from(sign));
+        assert_eq!(mag, be(half));
+
+        let (sign, mag) = (half + Fr::from(2u64)).to_centered_bytes_be();
+        assert!(bool::from(sign));
+        assert_eq!(mag, be(half - Fr::ONE));
+    }
+
+    #[test]
+    fn test_rem_is_zero() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            assert_eq!(&a % &b, Fr::ZERO);
+            assert_eq!(a % b, Fr::ZERO);
+        }
+        assert_eq!(Fr::from(7u64) % Fr::from(3u64), Fr::ZERO);
+        assert_eq!(Fr::ZERO % Fr::ONE, Fr::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rem_by_zero() {
+        let _ = Fr::ONE % Fr::ZERO;
+    }
+
+    #[test]
+    fn test_to_repr_reduced() {
+        let m = Fr::modulus_limbs();
+        // `MODULUS + 5`, which the zkVM backend keeps unreduced.
+        let x = Fr::from_raw([m[0] + 5, m[1], m[2], m[3]]);
+        let repr = x.to_repr_reduced();
+        assert_eq!(repr, Fr::from(5u64).to_repr());
+        assert_eq!(Fr::from_repr(repr).unwrap(), Fr::from(5u64));
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            assert_eq!(a.to_repr_reduced(), a.to_repr());
+        }
+    }
+
+    #[test]
+    fn test_pow_ct() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            let exp = [
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ];
+            assert_eq!(a.pow_ct(&exp), a.pow(exp));
+        }
+
+        let a = Fr::from(3u64);
+        assert_eq!(a.pow_ct(&[0; 4]), Fr::ONE);
+        assert_eq!(a.pow_ct(&[0xf; 4]), a.pow([0xf; 4]));
+        assert_eq!(a.pow_ct(&[u64::MAX; 4]), a.pow([u64::MAX; 4]));
+        assert_eq!(Fr::ZERO.pow_ct(&[5, 0, 0, 0]), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_invert_or_one() {
+        assert_eq!(Fr::ZERO.invert_or_one(), Fr::ONE);
+        assert_eq!(Fr::ONE.invert_or_one(), Fr::ONE);
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            assert_eq!(a.invert_or_one(), a.invert().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_batch_invert() {
+        use ff::BatchInvert;
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut elements: Vec<Fr> = (0..10).map(|_| Fr::random(&mut rng)).collect();
+        elements[0] = Fr::ZERO;
+        elements[4] = Fr::ZERO;
+        elements[9] = Fr::ZERO;
+
+        let mut expected = elements.clone();
+        expected.iter_mut().batch_invert();
+
+        let original = elements.clone();
+        Fr::batch_invert(&mut elements);
+        assert_eq!(elements, expected);
+        for (x, inv) in original.iter().zip(elements.iter()) {
+            if *x == Fr::ZERO {
+                assert_eq!(*inv, Fr::ZERO);
+            } else {
+                assert_eq!(x * inv, Fr::ONE);
+            }
+        }
+
+        Fr::batch_invert(&mut []);
+    }
+
+    #[test]
+    fn test_assign_operator_impls() {
+        use core::ops::{AddAssign, MulAssign, SubAssign};
+
+        fn assert_assign_ops<T>()
+        where
+            T: AddAssign<T> + for<'a> AddAssign<&'a T>,
+            T: SubAssign<T> + for<'a> SubAssign<&'a T>,
+            T: MulAssign<T> + for<'a> MulAssign<&'a T>,
+        {
+        }
+        assert_assign_ops::<Fr>();
+
+        let mut x = Fr::from(3u64);
+        x *= Fr::from(5u64);
+        x *= &Fr::from(2u64);
+        assert_eq!(x, Fr::from(30u64));
+    }
+
+    #[test]
+    fn test_pack_u128_pair() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let lo = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+            let hi = (((rng.next_u64() as u128) << 64) | rng.next_u64() as u128) >> 3;
+            let packed = Fr::pack_u128_pair(lo, hi).unwrap();
+            assert_eq!(packed.unpack_u128_pair(), (lo, hi));
+        }
+        assert_eq!(Fr::pack_u128_pair(0, 0).unwrap(), Fr::ZERO);
+        assert_eq!(
+            Fr::pack_u128_pair(0, 1).unwrap(),
+            Fr::from_raw([0, 0, 1, 0])
+        );
+
+        // The modulus itself is rejected, one below it is the largest element.
+        let m = Fr::modulus_limbs();
+        let m_lo = ((m[1] as u128) << 64) | m[0] as u128;
+        let m_hi = ((m[3] as u128) << 64) | m[2] as u128;
+        assert!(bool::from(Fr::pack_u128_pair(m_lo, m_hi).is_none()));
+        assert!(bool::from(
+            Fr::pack_u128_pair(u128::MAX, u128::MAX).is_none()
+        ));
+        let max = Fr::pack_u128_pair(m_lo - 1, m_hi).unwrap();
+        assert_eq!(max, -Fr::ONE);
+        assert_eq!(max.unpack_u128_pair(), (m_lo - 1, m_hi));
+    }
+
+    #[test]
+    fn test_checked_from_raw() {
+        assert_eq!(Fr::checked_from_raw([5, 0, 0, 0]), Some(Fr::from(5u64)));
+        assert_eq!(Fr::checked_from_raw([0; 4]), Some(Fr::ZERO));
+
+        let m = Fr::modulus_limbs();
+        assert_eq!(Fr::checked_from_raw(m), None);
+        assert_eq!(Fr::checked_from_raw([m[0] + 1, m[1], m[2], m[3]]), None);
+        assert_eq!(Fr::checked_from_raw([0, 0, 0, m[3] + 1]), None);
+        assert_eq!(Fr::checked_from_raw([u64::MAX; 4]), None);
+        assert_eq!(
+            Fr::checked_from_raw([m[0] - 1, m[1], m[2], m[3]]),
+            Some(-Fr::ONE)
+        );
+    }
+
+    #[test]
+    fn test_from_le_bytes_mod_order() {
+        assert_eq!(Fr::from_le_bytes_mod_order(&[]), Fr::ZERO);
+        assert_eq!(Fr::from_le_bytes_mod_order(&[7]), Fr::from(7u64));
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for len in [0, 1, 31, 32, 33, 48, 64, 100] {
+            for _ in 0..10 {
+                let mut bytes = vec![0u8; len];
+                rng.fill_bytes(&mut bytes);
+                assert_eq!(
+                    Fr::from_le_bytes_mod_order(&bytes),
+                    biguint_to_fr(&BigUint::from_bytes_le(&bytes))
+                );
+            }
+        }
+
+        // The modulus itself and all-ones inputs wrap around.
+        let modulus = modulus_biguint().to_bytes_le();
+        assert_eq!(Fr::from_le_bytes_mod_order(&modulus), Fr::ZERO);
+        assert_eq!(
+            Fr::from_le_bytes_mod_order(&[0xff; 48]),
+            biguint_to_fr(&BigUint::from_bytes_le(&[0xff; 48]))
+        );
+    }
+
+    #[test]
+    fn test_ct_eq_reduced() {
+        let m = Fr::modulus_limbs();
+        // `MODULUS + 5`, which the zkVM backend keeps unreduced.
+        let five = Fr::from_raw([m[0] + 5, m[1], m[2], m[3]]);
+        assert!(bool::from(five.ct_eq_reduced(&Fr::from(5u64))));
+        assert!(bool::from(Fr::from(5u64).ct_eq_reduced(&five)));
+        assert!(!bool::from(five.ct_eq_reduced(&Fr::from(6u64))));
+        assert!(bool::from(Fr::from_raw(m).ct_eq_reduced(&Fr::ZERO)));
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            assert!(bool::from(a.ct_eq_reduced(&a)));
+            assert_eq!(bool::from(a.ct_eq_reduced(&b)), a == b);
+        }
+    }
+
+    #[test]
+    fn test_eval_poly_multi() {
+        use crate::poly::Polynomial;
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let coeffs: Vec<Fr> = (0..17).map(|_| Fr::random(&mut rng)).collect();
+        let points: Vec<Fr> = (0..9).map(|_| Fr::random(&mut rng)).collect();
+        let poly = Polynomial(coeffs.clone());
+
+        let evals = Fr::eval_poly_multi(&coeffs, &points);
+        assert_eq!(evals.len(), points.len());
+        for (eval, x) in evals.iter().zip(&points) {
+            assert_eq!(*eval, poly.eval(x));
+        }
+
+        assert_eq!(Fr::eval_poly_multi(&coeffs, &[Fr::ZERO]), vec![coeffs[0]]);
+        assert_eq!(Fr::eval_poly_multi(&[], &points), vec![Fr::ZERO; 9]);
+        assert!(Fr::eval_poly_multi(&coeffs, &[]).is_empty());
+
+        #[cfg(feature = "rayon")]
+        assert_eq!(Fr::par_eval_poly_multi(&coeffs, &points), evals);
+    }
+
+    #[test]
+    fn test_bytes_be_trimmed() {
+        assert!(Fr::ZERO.to_bytes_be_trimmed().is_empty());
+        assert_eq!(Fr::from_bytes_be_padded(&[]).unwrap(), Fr::ZERO);
+        assert_eq!(Fr::from_bytes_be_padded(&[0]).unwrap(), Fr::ZERO);
+
+        assert_eq!(Fr::ONE.to_bytes_be_trimmed(), vec![1]);
+        assert_eq!(Fr::from(0x1234u64).to_bytes_be_trimmed(), vec![0x12, 0x34]);
+        assert_eq!(
+            Fr::from_bytes_be_padded(&[0, 0, 0x12, 0x34]).unwrap(),
+            Fr::from(0x1234u64)
+        );
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            let trimmed = a.to_bytes_be_trimmed();
+            assert!(trimmed.len() <= 32);
+            assert_ne!(trimmed.first(), Some(&0));
+            assert_eq!(Fr::from_bytes_be_padded(&trimmed).unwrap(), a);
+        }
+
+        assert_eq!((-Fr::ONE).to_bytes_be_trimmed().len(), 32);
+        assert!(bool::from(
+            Fr::from_bytes_be_padded(&Fr::modulus_bytes_be()).is_none()
+        ));
+        assert!(bool::from(Fr::from_bytes_be_padded(&[0; 33]).is_none()));
+    }
+
+    #[test]
+    fn test_conditional_inverse() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let a = Fr::random(&mut rng);
+            assert_eq!(a.conditional_inverse(Choice::from(0)).unwrap(), a);
+            assert_eq!(
+                a.conditional_inverse(Choice::from(1)).unwrap(),
+                a.invert().unwrap()
+            );
+        }
+
+        assert_eq!(
+            Fr::ZERO.conditional_inverse(Choice::from(0)).unwrap(),
+            Fr::ZERO
+        );
+        assert!(bool::from(
+            Fr::ZERO.conditional_inverse(Choice::from(1)).is_none()
+        ));
+    }
+
+    #[test]
+    fn test_from_u128() {
+        let mut expected = [0u8; 32];
+        expected[..16].copy_from_slice(&[0xff; 16]);
+        assert_eq!(Fr::from_u128(u128::MAX).to_repr(), expected);
+        assert_eq!(Fr::from_u128(u128::MAX), Fr::from(u128::MAX));
+
+        let two_64 = Fr::from_raw([0, 1, 0, 0]);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let (lo, hi) = (rng.next_u64(), rng.next_u64());
+            let v = ((hi as u128) << 64) | lo as u128;
+            assert_eq!(Fr::from_u128(v), Fr::from(hi) * two_64 + Fr::from(lo));
+        }
+        assert_eq!(Fr::from_u128(0), Fr::ZERO);
+        assert_eq!(Fr::from_u128(1), Fr::ONE);
+    }
+
+    #[test]
+    fn test_random_vec() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for n in [0, 1, 100] {
+            let v = Fr::random_vec(&mut rng, n);
+            assert_eq!(v.len(), n);
+            assert!(v
+                .iter()
+                .all(|x| Fr::from_repr(x.to_repr()).is_some().into()));
+        }
+
+        let v = Fr::random_vec(&mut rng, 100);
+        assert!(v.windows(2).all(|w| w[0] != w[1]));
+
+        let mut out = [Fr::ZERO; 50];
+        Fr::fill_random(&mut rng, &mut out);
+        assert!(out.iter().all(|x| x.is_canonical() && *x != Fr::ZERO));
+
+        // Same seed, same stream
+        let a = Fr::random_vec(StdRng::seed_from_u64(1), 10);
+        let mut b = [Fr::ZERO; 10];
+        Fr::fill_random(StdRng::seed_from_u64(1), &mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_sqrt_verify() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..20 {
+            let root = Fr::random(&mut rng);
+            let x = root.square();
+            assert!(bool::from(x.sqrt_verify(&root)));
+            assert!(bool::from(x.sqrt_verify(&-root)));
+            assert!(!bool::from(x.sqrt_verify(&(root + Fr::ONE))));
+            assert!(!bool::from(x.sqrt_verify(&x)));
+
+            // A non-residue has no valid candidate at all.
+            let non_residue = x * Fr::MULTIPLICATIVE_GENERATOR;
+            assert!(!bool::from(non_residue.sqrt_verify(&root)));
+        }
+        assert!(bool::from(Fr::ZERO.sqrt_verify(&Fr::ZERO)));
+    }
+
+    #[test]
+    fn test_try_into_u64() {
+        assert_eq!(Fr::ZERO.try_into_u64(), Some(0));
+        assert_eq!(Fr::from(42u64).try_into_u64(), Some(42));
+        assert_eq!(Fr::from(u64::MAX).try_into_u64(), Some(u64::MAX));
+
+        let two_limbs = Fr::from_raw([7, 1, 0, 0]);
+        assert_eq!(two_limbs.try_into_u64(), None);
+        assert_eq!(two_limbs.as_u64_lossy(), 7);
+        assert_eq!((-Fr::ONE).try_into_u64(), None);
+        assert_eq!((-Fr::ONE).as_u64_lossy(), Fr::modulus_limbs()[0] - 1);
+
+        assert_eq!(Fr::ZERO.as_u64_lossy(), 0);
+        assert_eq!(Fr::from(42u64).as_u64_lossy(), 42);
+    }
+
+    #[test]
+    fn test_add_scaled() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let coeffs = Fr::random_vec(&mut rng, 50);
+        let points = Fr::random_vec(&mut rng, 50);
+
+        let mut acc = Fr::ZERO;
+        let mut expected = Fr::ZERO;
+        for (c, x) in coeffs.iter().zip(&points) {
+            acc.add_scaled(c, x);
+            expected += c * x;
+        }
+        assert_eq!(acc, expected);
+        assert_eq!(acc, Fr::linear_combination(&coeffs, &points));
+    }
+
+    #[test]
+    fn test_fr_vec_macro() {
+        let table = crate::fr_vec![
+            "0",
+            "1",
+            "12345678901234567890",
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616",
+        ];
+        assert_eq!(
+            table,
+            [
+                Fr::ZERO,
+                Fr::ONE,
+                Fr::from(12345678901234567890u64),
+                -Fr::ONE
+            ]
+        );
+
+        let empty: [Fr; 0] = crate::fr_vec![];
+        assert!(empty.is_empty());
+        assert_eq!(crate::fr_vec!["7",], [Fr::from(7u64)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fr_vec_macro_rejects_modulus() {
+        let _ = crate::fr_vec![
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        ];
+    }
+
+    #[test]
+    fn test_halve() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..1000 {
+            let x = Fr::random(&mut rng);
+            assert_eq!(x.halve().double(), x);
+            assert_eq!(x.double().halve(), x);
+        }
+
+        assert_eq!(Fr::ZERO.halve(), Fr::ZERO);
+        assert_eq!(Fr::from(10u64).halve(), Fr::from(5u64));
+        // 1 / 2 is (MODULUS + 1) / 2.
+        assert_eq!(Fr::ONE.halve(), <Fr as PrimeField>::TWO_INV);
+        assert_eq!((-Fr::ONE).halve(), -<Fr as PrimeField>::TWO_INV);
+    }
+
+    #[test]
+    fn test_from_bytes_wide_known_answers() {
+        let mut bytes = [0u8; 64];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let [le, be, all_ones] = crate::fr_vec![
+            "12013539567687322724563591696141680761088723402739581838264091936971283177716",
+            "8563105810894278686461714224034664986130416497486124215490399601617240529733",
+            "944936681149208446651664254269745548490766851729442924617792859073125903782",
+        ];
+        assert_eq!(Fr::from_bytes_le_wide(&bytes), le);
+        assert_eq!(Fr::from_bytes_be_wide(&bytes), be);
+        assert_eq!(Fr::from_bytes_le_wide(&[0xff; 64]), all_ones);
+        assert_eq!(Fr::from_bytes_be_wide(&[0xff; 64]), all_ones);
+
+        // Small values land in the expected end of the buffer.
+        let mut one_le = [0u8; 64];
+        one_le[0] = 1;
+        let mut one_be = [0u8; 64];
+        one_be[63] = 1;
+        assert_eq!(Fr::from_bytes_le_wide(&one_le), Fr::ONE);
+        assert_eq!(Fr::from_bytes_be_wide(&one_be), Fr::ONE);
+    }
+
+    #[test]
+    fn test_from_bytes_wide_byte_orders_agree() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            let le = Fr::from_bytes_le_wide(&bytes);
+            assert_eq!(
+                le,
+                <Fr as ff::FromUniformBytes<64>>::from_uniform_bytes(&bytes)
+            );
+            bytes.reverse();
+            assert_eq!(Fr::from_bytes_be_wide(&bytes), le);
+        }
+    }
+
+    #[test]
+    fn test_into_byte_and_limb_arrays() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let x = Fr::random(&mut rng);
+
+            let bytes: [u8; 32] = x.into();
+            assert_eq!(bytes, x.to_repr());
+            assert_eq!(Fr::from_repr(bytes).unwrap(), x);
+            assert_eq!(<[u8; 32]>::from(&x), bytes);
+
+            let limbs: [u64; 4] = x.into();
+            assert_eq!(Fr::from_raw(limbs), x);
+            for (limb, chunk) in limbs.iter().zip(bytes.chunks_exact(8)) {
+                assert_eq!(limb.to_le_bytes(), chunk);
+            }
+        }
+
+        assert_eq!(<[u64; 4]>::from(Fr::ONE), [1, 0, 0, 0]);
+        let m = Fr::modulus_limbs();
+        assert_eq!(<[u64; 4]>::from(-Fr::ONE), [m[0] - 1, m[1], m[2], m[3]]);
+    }
+
+    #[test]
+    fn test_batch_add_sub() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let a = Fr::random_vec(&mut rng, 100);
+        let b = Fr::random_vec(&mut rng, 100);
+
+        let mut sum = vec![Fr::ZERO; 100];
+        let mut diff = vec![Fr::ZERO; 100];
+        Fr::batch_add(&a, &b, &mut sum);
+        Fr::batch_sub(&a, &b, &mut diff);
+        for (((s, d), x), y) in sum.iter().zip(&diff).zip(&a).zip(&b) {
+            assert_eq!(*s, x + y);
+            assert_eq!(*d, x - y);
+        }
+
+        Fr::batch_add(&[], &[], &mut []);
+        Fr::batch_sub(&[], &[], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_batch_add_length_mismatch() {
+        Fr::batch_add(&[Fr::ONE; 3], &[Fr::ONE; 2], &mut [Fr::ZERO; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_batch_sub_output_length_mismatch() {
+        Fr::batch_sub(&[Fr::ONE; 3], &[Fr::ONE; 3], &mut [Fr::ZERO; 4]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_batch_add_sub() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let a = Fr::random_vec(&mut rng, 5000);
+        let b = Fr::random_vec(&mut rng, 5000);
+
+        let (mut expected, mut actual) = (vec![Fr::ZERO; 5000], vec![Fr::ZERO; 5000]);
+        Fr::batch_add(&a, &b, &mut expected);
+        Fr::par_batch_add(&a, &b, &mut actual);
+        assert_eq!(actual, expected);
+
+        Fr::batch_sub(&a, &b, &mut expected);
+        Fr::par_batch_sub(&a, &b, &mut actual);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_in_subgroup_of_order_2k() {
+        for k in 0..=Fr::S {
+            let root = Fr::root_of_unity(k);
+            assert!(bool::from(root.in_subgroup_of_order_2k(k)));
+            assert!(bool::from(root.in_subgroup_of_order_2k(Fr::S + 1)));
+            if k > 0 {
+                // Primitive, so not in the next smaller subgroup.
+                assert!(!bool::from(root.in_subgroup_of_order_2k(k - 1)));
+            }
+        }
+
+        for k in 0..8 {
+            assert!(!bool::from(
+                Fr::MULTIPLICATIVE_GENERATOR.in_subgroup_of_order_2k(k)
+            ));
+            assert!(!bool::from(Fr::ZERO.in_subgroup_of_order_2k(k)));
+        }
+        assert!(bool::from(Fr::ONE.in_subgroup_of_order_2k(0)));
+        assert!(bool::from((-Fr::ONE).in_subgroup_of_order_2k(1)));
+        assert!(!bool::from((-Fr::ONE).in_subgroup_of_order_2k(0)));
+    }
+
+    #[cfg(feature = "rand-seed")]
+    #[test]
+    fn test_random_from_seed() {
+        let a = Fr::random_from_seed([7; 32]);
+        assert_eq!(a, Fr::random_from_seed([7; 32]));
+        assert_ne!(a, Fr::random_from_seed([8; 32]));
+
+        let mut seed = [0u8; 32];
+        let samples: Vec<Fr> = (0..100u8)
+            .map(|i| {
+                seed[0] = i;
+                Fr::random_from_seed(seed)
+            })
+            .collect();
+        for (i, x) in samples.iter().enumerate() {
+            assert!(!samples[..i].contains(x));
+        }
+    }
+
+    #[test]
+    fn test_order_bytes_be() {
+        assert_eq!(
+            Fr::NUM_BYTES,
+            core::mem::size_of::<<Fr as PrimeField>::Repr>()
+        );
+        assert!(Fr::NUM_BYTES * 8 >= Fr::NUM_BITS as usize);
+
+        let bytes = Fr::order_bytes_be();
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        assert_eq!(limbs, Fr::modulus_limbs());
+        assert_eq!(BigUint::from_bytes_be(&bytes), modulus_biguint());
+    }
+
+    #[test]
+    fn test_batch_invert_flags() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let zeros = [0, 3, 4, 9];
+        let original: Vec<Fr> = (0..10)
+            .map(|i| {
+                if zeros.contains(&i) {
+                    Fr::ZERO
+                } else {
+                    Fr::random(&mut rng)
+                }
+            })
+            .collect();
+
+        let mut elements = original.clone();
+        let flags = Fr::batch_invert_flags(&mut elements);
+        assert_eq!(flags.len(), original.len());
+        for (i, ((flag, x), inv)) in flags.iter().zip(&original).zip(&elements).enumerate() {
+            if zeros.contains(&i) {
+                assert!(!bool::from(*flag));
+                assert_eq!(*inv, Fr::ZERO);
+            } else {
+                assert!(bool::from(*flag));
+                assert_eq!(*inv, x.invert().unwrap());
+            }
+        }
+
+        assert!(Fr::batch_invert_flags(&mut []).is_empty());
+    }
+
+    #[test]
+    fn test_u64_operands() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for k in [0u64, 1, 3, 17, u64::MAX] {
+            let x = Fr::random(&mut rng);
+            let k_fr = Fr::from(k);
+
+            assert_eq!(x * k, x * k_fr);
+            assert_eq!(&x * k, x * k_fr);
+            assert_eq!(x * &k, x * k_fr);
+            assert_eq!(&x * &k, x * k_fr);
+            assert_eq!(x * k, x.mul_by_u64(k));
+
+            assert_eq!(x + k, x + k_fr);
+            assert_eq!(&x + k, x + k_fr);
+            assert_eq!(x + &k, x + k_fr);
+            assert_eq!(&x + &k, x + k_fr);
+
+            let mut y = x;
+            y *= k;
+            y += k;
+            assert_eq!(y, x * k_fr + k_fr);
+        }
+
+        // Reduced as a field element, not wrapped as an integer.
+        assert_eq!(-Fr::ONE + 1u64, Fr::ZERO);
+        assert_eq!(Fr::from(u64::MAX) + 1u64, Fr::from_u128(1 << 64));
+    }
+
+    #[test]
+    fn test_debug_decimal() {
+        assert_eq!(Fr::ZERO.debug_decimal(), "0");
+        assert_eq!(Fr::ONE.debug_decimal(), "1");
+        assert_eq!(
+            Fr::from(10_000_000_000_000_000_000u128).debug_decimal(),
+            "10000000000000000000"
+        );
+        assert_eq!(
+            (-Fr::ONE).debug_decimal(),
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616"
+        );
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let x = Fr::random(&mut rng);
+            let decimal = x.debug_decimal();
+            assert_eq!(decimal, BigUint::from_bytes_le(&x.to_repr()).to_string());
+            assert_eq!(Fr::from_str_vartime(&decimal).unwrap(), x);
+        }
+    }
+
+    #[test]
+    fn test_assert_fr_eq() {
+        let x = Fr::from(6u64);
+        crate::assert_fr_eq!(x, Fr::from(2u64) * Fr::from(3u64));
+        crate::assert_fr_eq!(&x, &x,);
+        crate::assert_fr_eq!(x.square(), Fr::from(36u64), "square of {}", 6);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "left: 1\n right: 21888242871839275222246405745257275088548364400416034343698204186575808495616"
+    )]
+    fn test_assert_fr_eq_failure_message() {
+        crate::assert_fr_eq!(Fr::ONE, -Fr::ONE);
+    }
+
+    #[test]
+    fn test_ff_batch_inverter() {
+        use ff::{BatchInvert, BatchInverter};
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let original = Fr::random_vec(&mut rng, 50);
+        let expected: Vec<Fr> = original.iter().map(|x| x.invert().unwrap()).collect();
+
+        let mut elements = original.clone();
+        let mut scratch = vec![Fr::ZERO; elements.len()];
+        let ok = BatchInverter::invert_with_external_scratch(&mut elements, &mut scratch);
+        assert!(bool::from(ok));
+        assert_eq!(elements, expected);
+
+        // Elements embedded in a larger struct, with the scratch alongside.
+        let mut items: Vec<(Fr, Fr)> = original.iter().map(|x| (*x, Fr::ZERO)).collect();
+        let ok =
+            BatchInverter::invert_with_internal_scratch(&mut items, |i| &mut i.0, |i| &mut i.1);
+        assert!(bool::from(ok));
+        assert!(items.iter().map(|i| i.0).eq(expected.iter().copied()));
+
+        let mut elements = original.clone();
+        let product_inv = elements.iter_mut().batch_invert();
+        assert_eq!(elements, expected);
+        assert_eq!(
+            product_inv,
+            original.iter().product::<Fr>().invert().unwrap()
+        );
+
+        // A zero anywhere makes the whole batch fail.
+        let mut elements = original.clone();
+        elements[7] = Fr::ZERO;
+        let ok = BatchInverter::invert_with_external_scratch(&mut elements, &mut scratch);
+        assert!(!bool::from(ok));
+    }
+
+    #[test]
+    fn test_neg_assign() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for x in Fr::random_vec(&mut rng, 100)
+            .into_iter()
+            .chain([Fr::ZERO, Fr::ONE])
+        {
+            let mut y = x;
+            y.neg_assign();
+            assert_eq!(y, -x);
+            assert_eq!(x + y, Fr::ZERO);
+            y.neg_assign();
+            assert_eq!(y, x);
+        }
+    }
+
+    #[test]
+    fn test_pow_ladder() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..50 {
+            let x = Fr::random(&mut rng);
+            let exp = [
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ];
+            assert_eq!(x.pow_ladder(&exp), x.pow(exp));
+            assert_eq!(x.pow_ladder(&exp), x.pow_ct(&exp));
+        }
+
+        let x = Fr::from(3u64);
+        assert_eq!(x.pow_ladder(&[0; 4]), Fr::ONE);
+        assert_eq!(x.pow_ladder(&[1, 0, 0, 0]), x);
+        assert_eq!(x.pow_ladder(&[5, 0, 0, 0]), Fr::from(243u64));
+        assert_eq!(Fr::ZERO.pow_ladder(&[0; 4]), Fr::ONE);
+        assert_eq!(Fr::ZERO.pow_ladder(&[7, 0, 0, 0]), Fr::ZERO);
+
+        // x^(MODULUS - 1) = 1 for nonzero x
+        let m = Fr::modulus_limbs();
+        assert_eq!(x.pow_ladder(&[m[0] - 1, m[1], m[2], m[3]]), Fr::ONE);
+        assert_eq!(x.pow_ladder(&[u64::MAX; 4]), x.pow([u64::MAX; 4]));
+    }
+
+    #[test]
+    fn test_from_u256_limbs_checked() {
+        let m = Fr::modulus_limbs();
+        assert_eq!(
+            Fr::from_u256_limbs_checked([m[0] - 1, m[1], m[2], m[3]]),
+            Ok(-Fr::ONE)
+        );
+        assert_eq!(Fr::from_u256_limbs_checked(m), Err(Boundary::EqualsModulus));
+        assert_eq!(
+            Fr::from_u256_limbs_checked([m[0] + 1, m[1], m[2], m[3]]),
+            Err(Boundary::AboveModulus)
+        );
+        // Larger only in a higher limb
+        assert_eq!(
+            Fr::from_u256_limbs_checked([0, 0, 0, m[3] + 1]),
+            Err(Boundary::AboveModulus)
+        );
+        assert_eq!(
+            Fr::from_u256_limbs_checked([u64::MAX; 4]),
+            Err(Boundary::AboveModulus)
+        );
+        assert_eq!(Fr::from_u256_limbs_checked([0; 4]), Ok(Fr::ZERO));
+        assert_eq!(
+            Fr::from_u256_limbs_checked([u64::MAX, u64::MAX, 0, 0]),
+            Ok(Fr::from_u128(u128::MAX))
+        );
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        let x = Fr::from(3u64);
+        let y = -Fr::from(5u64);
+
+        let (mut a, mut b) = (x, y);
+        Fr::conditional_swap(&mut a, &mut b, Choice::from(0));
+        assert_eq!((a, b), (x, y));
+
+        Fr::conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((a, b), (y, x));
+
+        Fr::conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((a, b), (x, y));
+    }
+
+    #[test]
+    fn test_div_2k() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let x = Fr::random(&mut rng);
+            assert_eq!(x.div_2k(3).double().double().double(), x);
+            assert_eq!(x.div_2k(0), x);
+            assert_eq!(x.div_2k(1), x.halve());
+            assert_eq!(x.div_2k(5), x.halve().halve().halve().halve().halve());
+        }
+
+        assert_eq!(Fr::from(96u64).div_2k(5), Fr::from(3u64));
+        // 2^300 wraps around the modulus several times.
+        assert_eq!(Fr::from(2u64).pow_vartime([300]).div_2k(300), Fr::ONE);
+    }
+
+    #[test]
+    fn test_canonical_cmp() {
+        use core::cmp::Ordering;
+
+        let two = Fr::from(2u64);
+        assert_eq!(Fr::ONE.canonical_cmp(&two), Ordering::Less);
+        assert_eq!(two.canonical_cmp(&two), Ordering::Equal);
+        assert_eq!(two.canonical_cmp(&Fr::ONE), Ordering::Greater);
+        // Compared as integers: -1 is the largest value.
+        assert_eq!((-Fr::ONE).canonical_cmp(&two), Ordering::Greater);
+        assert_eq!(Fr::ZERO.canonical_cmp(&-Fr::ONE), Ordering::Less);
+        // A higher byte dominates lower ones.
+        assert_eq!(
+            Fr::from(1u64 << 8).canonical_cmp(&Fr::from(0xffu64)),
+            Ordering::Greater
+        );
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            let expected =
+                BigUint::from_bytes_le(&a.to_repr()).cmp(&BigUint::from_bytes_le(&b.to_repr()));
+            assert_eq!(a.canonical_cmp(&b), expected);
+            assert_eq!(b.canonical_cmp(&a), expected.reverse());
+        }
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv_roundtrip() {
+        use rkyv::Deserialize;
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let elements = Fr::random_vec(&mut rng, 16);
+
+        let x = elements[0];
+        let bytes = rkyv::to_bytes::<_, 256>(&x).unwrap();
+        let archived = unsafe { rkyv::archived_root::<Fr>(&bytes) };
+        assert_eq!(archived.limbs(), <[u64; 4]>::from(x));
+        assert_eq!(archived.to_fr(), x);
+        assert_eq!(archived.to_fr_checked(), Some(x));
+        let back: Fr = archived.deserialize(&mut rkyv::Infallible).unwrap();
+        assert_eq!(back, x);
+
+        // Elements are read in place from the archived vector.
+        let bytes = rkyv::to_bytes::<_, 1024>(&elements).unwrap();
+        let archived = unsafe { rkyv::archived_root::<Vec<Fr>>(&bytes) };
+        assert_eq!(archived.len(), elements.len());
+        for (a, x) in archived.iter().zip(&elements) {
+            assert_eq!(a.to_fr(), *x);
+        }
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv_non_canonical() {
+        let archived = ArchivedFr(Fr::modulus_limbs().map(Into::into));
+        assert_eq!(archived.to_fr_checked(), None);
+        assert_eq!(archived.to_fr(), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_lagrange_eval() {
+        // p(x) = 3x^2 - 5x + 7
+        let p = |x: &Fr| Fr::from(3u64) * x.square() - Fr::from(5u64) * x + Fr::from(7u64);
+        let points: Vec<(Fr, Fr)> = [1u64, 4, 10]
+            .iter()
+            .map(|&x| (Fr::from(x), p(&Fr::from(x))))
+            .collect();
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let targets = [
+            Fr::ZERO,
+            Fr::from(2u64),
+            Fr::from(4u64),
+            -Fr::ONE,
+            Fr::random(&mut rng),
+        ];
+        for at in targets {
+            assert_eq!(Fr::lagrange_eval(&points, &at).unwrap(), p(&at));
+        }
+
+        // More points than needed still give the same quadratic.
+        let mut more = points.clone();
+        more.push((Fr::from(100u64), p(&Fr::from(100u64))));
+        assert_eq!(
+            Fr::lagrange_eval(&more, &Fr::from(7u64)).unwrap(),
+            p(&Fr::from(7u64))
+        );
+
+        let single = [(Fr::from(3u64), Fr::from(9u64))];
+        assert_eq!(
+            Fr::lagrange_eval(&single, &Fr::ONE).unwrap(),
+            Fr::from(9u64)
+        );
+        assert_eq!(Fr::lagrange_eval(&[], &Fr::ONE).unwrap(), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_lagrange_eval_duplicate_x() {
+        let points = [
+            (Fr::ONE, Fr::from(2u64)),
+            (Fr::from(5u64), Fr::from(3u64)),
+            (Fr::ONE, Fr::from(4u64)),
+        ];
+        assert!(bool::from(Fr::lagrange_eval(&points, &Fr::ZERO).is_none()));
+    }
+
+    #[test]
+    fn test_neg_to_repr() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for x in Fr::random_vec(&mut rng, 100) {
+            assert_eq!(x.neg_to_repr(), (-x).to_repr());
+        }
+        assert_eq!(Fr::ZERO.neg_to_repr(), [0; 32]);
+        assert_eq!((-Fr::ONE).neg_to_repr(), Fr::ONE.to_repr());
+        assert_eq!(Fr::ONE.neg_to_repr(), (-Fr::ONE).to_repr());
+        // A borrow out of the lowest limb
+        let x = Fr::from(u64::MAX);
+        assert_eq!(x.neg_to_repr(), (-x).to_repr());
+    }
+
+    #[test]
+    fn test_checked_sub_assign() {
+        let mut x = Fr::from(5u64);
+        assert!(!x.checked_sub_assign(&Fr::from(3u64)));
+        assert_eq!(x, Fr::from(2u64));
+
+        let mut x = Fr::from(3u64);
+        assert!(x.checked_sub_assign(&Fr::from(5u64)));
+        assert_eq!(x, -Fr::from(2u64));
+
+        let mut x = Fr::from(7u64);
+        assert!(!x.checked_sub_assign(&Fr::from(7u64)));
+        assert_eq!(x, Fr::ZERO);
+
+        let mut x = Fr::ZERO;
+        assert!(x.checked_sub_assign(&Fr::ONE));
+        assert_eq!(x, -Fr::ONE);
+    }
+
+    #[cfg(all(
+        feature = "entropy",
+        not(all(target_os = "zkvm", target_vendor = "succinct"))
+    ))]
+    #[test]
+    fn test_from_entropy() {
+        let samples: Vec<Fr> = (0..10).map(|_| Fr::from_entropy()).collect();
+        for (i, x) in samples.iter().enumerate() {
+            assert!(Option::<Fr>::from(Fr::from_repr(x.to_repr())).is_some());
+            assert!(!bool::from(x.is_zero()));
+            // A collision among 10 draws has negligible probability.
+            assert!(!samples[..i].contains(x));
+        }
+    }
+
+    /// Checks `invert` and `sqrt` on `x` through the `Field` trait alone, the
+    /// way generic code sees the field.
+    fn roundtrip<F: Field>(x: F) {
+        assert!(bool::from(F::ZERO.invert().is_none()));
+        let inv = x.invert();
+        assert_eq!(bool::from(inv.is_some()), !bool::from(x.is_zero()));
+        if let Some(inv) = Option::<F>::from(inv) {
+            assert_eq!(x * inv, F::ONE);
+            assert_eq!(inv.invert().unwrap(), x);
+        }
+
+        let x2 = x.square();
+        let root = x2.sqrt().unwrap();
+        assert_eq!(root.square(), x2);
+        assert!(root == x || root == -x);
+        if let Some(r) = Option::<F>::from(x.sqrt()) {
+            assert_eq!(r.square(), x);
+        }
+    }
+
+    /// Checks the four cases of the `Field::sqrt_ratio` contract.
+    fn sqrt_ratio_contract<F: PrimeField>(num: F, div: F) {
+        let (is_square, res) = F::sqrt_ratio(&num, &div);
+        if bool::from(num.is_zero()) {
+            assert!(bool::from(is_square));
+            assert_eq!(res, F::ZERO);
+        } else if bool::from(div.is_zero()) {
+            assert!(!bool::from(is_square));
+            assert_eq!(res, F::ZERO);
+        } else {
+            let ratio = num * div.invert().unwrap();
+            assert_eq!(bool::from(is_square), bool::from(ratio.sqrt().is_some()));
+            if bool::from(is_square) {
+                assert_eq!(res.square(), ratio);
+            } else {
+                assert_eq!(res.square(), F::ROOT_OF_UNITY * ratio);
+            }
+        }
+    }
+
+    #[test]
+    fn test_field_trait_contract() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut values = Fr::random_vec(&mut rng, 50);
+        values.extend([Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::MULTIPLICATIVE_GENERATOR]);
+
+        for x in &values {
+            roundtrip(*x);
+        }
+        let mut squares = 0;
+        for num in &values {
+            for div in [Fr::ZERO, Fr::ONE, Fr::MULTIPLICATIVE_GENERATOR, values[0]] {
+                sqrt_ratio_contract(*num, div);
+            }
+            squares += bool::from(Fr::sqrt_ratio(num, &Fr::ONE).0) as usize;
+        }
+        // Both square and non-square ratios were exercised.
+        assert!(squares > 0 && squares < values.len());
+        // The generator is a non-square.
+        assert!(bool::from(Fr::MULTIPLICATIVE_GENERATOR.sqrt().is_none()));
+    }
+
+    #[test]
+    fn test_powers_into() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let base = Fr::random(&mut rng);
+
+        let mut out = [Fr::ZERO; 10];
+        Fr::powers_into(&base, &mut out);
+        assert_eq!(out.to_vec(), Fr::powers(&base, 10));
+        for (i, p) in out.iter().enumerate() {
+            assert_eq!(*p, base.pow_vartime_u64(i as u64));
+        }
+
+        let mut empty: [Fr; 0] = [];
+        Fr::powers_into(&base, &mut empty);
+        assert!(Fr::powers(&base, 0).is_empty());
+
+        let mut one = [base];
+        Fr::powers_into(&base, &mut one);
+        assert_eq!(one, [Fr::ONE]);
+
+        // 0^0 = 1
+        assert_eq!(Fr::powers(&Fr::ZERO, 3), vec![Fr::ONE, Fr::ZERO, Fr::ZERO]);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck_cast() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let elements = Fr::random_vec(&mut rng, 8);
+
+        let bytes: &[u8] = bytemuck::cast_slice(&elements);
+        assert_eq!(bytes.len(), 32 * elements.len());
+        let copied = bytes.to_vec();
+
+        // Back through an aligned buffer, since a `Vec<u8>` may not be.
+        let mut back = vec![Fr::ZERO; elements.len()];
+        bytemuck::cast_slice_mut::<Fr, u8>(&mut back).copy_from_slice(&copied);
+        assert_eq!(back, elements);
+        assert_eq!(bytemuck::cast_slice::<u8, Fr>(bytes), &elements[..]);
+
+        assert_eq!(<Fr as bytemuck::Zeroable>::zeroed(), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_inv_2k() {
+        assert_eq!(Fr::inv_2k(3) * Fr::from(8u64), Fr::ONE);
+        assert_eq!(Fr::inv_2k(0), Fr::ONE);
+        assert_eq!(Fr::inv_2k(1), <Fr as PrimeField>::TWO_INV);
+        for k in [5, 28, 64, 253, 300] {
+            let two_k = Fr::from(2u64).pow_vartime_u64(k as u64);
+            assert_eq!(Fr::inv_2k(k), two_k.invert().unwrap());
+            assert_eq!(Fr::inv_2k(k) * two_k, Fr::ONE);
+        }
+    }
+
+    #[test]
+    fn test_two_inv_limbs() {
+        let two_inv = Fr::from_raw(TWO_INV_LIMBS);
+        assert_eq!(two_inv.double(), Fr::ONE);
+        assert_eq!(<Fr as PrimeField>::TWO_INV, two_inv);
+        assert_eq!(<[u64; 4]>::from(Fr::TWO_INV), TWO_INV_LIMBS);
+    }
+
+    #[test]
+    fn test_shared_sqrt() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..50 {
+            let x = Fr::random(&mut rng);
+            let root = sqrt(&x.square()).unwrap();
+            assert!(root == x || root == -x);
+            let (is_square, ratio_root) = sqrt_ratio(&x.square(), &Fr::ONE);
+            assert!(bool::from(is_square));
+            assert_eq!(ratio_root.square(), x.square());
+        }
+        assert_eq!(sqrt(&Fr::ZERO).unwrap(), Fr::ZERO);
+        // The multiplicative generator is a non-residue.
+        assert!(bool::from(sqrt(&Fr::MULTIPLICATIVE_GENERATOR).is_none()));
+        let (is_square, _) = sqrt_ratio(&Fr::MULTIPLICATIVE_GENERATOR, &Fr::ONE);
+        assert!(!bool::from(is_square));
+    }
+
+    #[test]
+    fn test_from_bool() {
+        assert_eq!(Fr::from(true), Fr::ONE);
+        assert_eq!(Fr::from(false), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_from_u64() {
+        assert_eq!(Fr::from(0u64), Fr::ZERO);
+        assert_eq!(Fr::from(1u64), Fr::ONE);
+        let max = Fr::from(u64::MAX);
+        assert_eq!(<[u64; 4]>::from(max), [u64::MAX, 0, 0, 0]);
+        assert_eq!(max + Fr::ONE, Fr::from_u128(1 << 64));
+    }
+}
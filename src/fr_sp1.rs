@@ -1,10 +1,13 @@
 use super::ff::*;
 use core::fmt::{self, Debug, Display};
 use core::iter::{Product, Sum};
-use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::ops::{Add, Mul, Neg, Sub};
 use rand_core::RngCore;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
+use crate::fr_common;
+use crate::{impl_binops_additive, impl_mul_assign};
+
 #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
 use sp1_intrinsics;
 
@@ -18,9 +21,59 @@ const MODULUS: [u64; 4] = [
     0x30644e72e131a029,
 ];
 
-#[derive(Copy, Clone, Default, PartialEq, Eq)]
+/// The modulus as u32 limbs.
+#[cfg(not(target_pointer_width = "64"))]
+const MODULUS_LIMBS_32: [u32; 8] = [
+    0xf000_0001,
+    0x43e1_f593,
+    0x79b9_7091,
+    0x2833_e848,
+    0x8181_585d,
+    0xb850_45b6,
+    0xe131_a029,
+    0x3064_4e72,
+];
+
+#[derive(Copy, Clone, Default, Eq)]
+#[repr(transparent)]
 pub struct Fr(pub [u64; 4]);
 
+/// Subtracts `MODULUS` from `limbs` if `limbs >= MODULUS`, in constant time.
+const fn sub_modulus_if_geq(limbs: &[u64; 4]) -> [u64; 4] {
+    let mut diff = [0u64; 4];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 4 {
+        let (d, b1) = limbs[i].overflowing_sub(MODULUS[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        diff[i] = d;
+        borrow = b1 | b2;
+        i += 1;
+    }
+
+    // `mask` is all ones if there was no final borrow, i.e. `limbs >= MODULUS`.
+    let mask = (borrow as u64).wrapping_sub(1);
+    [
+        (diff[0] & mask) | (limbs[0] & !mask),
+        (diff[1] & mask) | (limbs[1] & !mask),
+        (diff[2] & mask) | (limbs[2] & !mask),
+        (diff[3] & mask) | (limbs[3] & !mask),
+    ]
+}
+
+/// Reduces an arbitrary 256-bit integer modulo `MODULUS` in constant time.
+/// Every such integer is less than `6 * MODULUS`, so five conditional
+/// subtractions always suffice.
+const fn reduce(limbs: &[u64; 4]) -> [u64; 4] {
+    let mut res = *limbs;
+    let mut i = 0;
+    while i < 5 {
+        res = sub_modulus_if_geq(&res);
+        i += 1;
+    }
+    res
+}
+
 impl Fr {
     #[inline]
     pub const fn zero() -> Self {
@@ -43,25 +96,69 @@ impl Fr {
             limbs[i] = val;
         }
 
-        // Check if value is less than modulus
-        let mut is_less = false;
-        for i in (0..4).rev() {
-            if limbs[i] < MODULUS[i] {
-                is_less = true;
-                break;
-            }
-            if limbs[i] > MODULUS[i] {
-                break;
-            }
+        // `limbs < MODULUS` iff subtracting the modulus borrows out of the top
+        // limb; equal to the modulus does not borrow and is rejected. This
+        // does not branch on the input.
+        let mut borrow = 0u128;
+        for (limb, m) in limbs.iter().zip(MODULUS.iter()) {
+            let diff = (*limb as u128).wrapping_sub(*m as u128 + borrow);
+            borrow = diff >> 127;
         }
 
-        CtOption::new(Fr(limbs), Choice::from(is_less as u8))
+        CtOption::new(Fr(limbs), Choice::from(borrow as u8))
     }
 
     pub const fn from_raw(limbs: [u64; 4]) -> Fr {
         Fr(limbs)
     }
 
+    /// Returns the field modulus as little-endian 64-bit limbs.
+    pub const fn modulus_limbs() -> [u64; 4] {
+        MODULUS
+    }
+
+    /// Returns whether the limbs are less than the modulus. `from_raw` and the
+    /// public tuple field allow non-canonical limbs, which the arithmetic
+    /// routines do not handle; they check this in debug builds.
+    ///
+    /// This runs in variable time.
+    pub fn is_canonical(&self) -> bool {
+        for i in (0..4).rev() {
+            if self.0[i] != MODULUS[i] {
+                return self.0[i] < MODULUS[i];
+            }
+        }
+        false
+    }
+
+    /// Reduces the limbs of every element modulo the modulus, in place, so
+    /// that values loaded through `from_raw` or the public tuple field from
+    /// stale or untrusted dumps become canonical again.
+    pub fn batch_normalize(elements: &mut [Fr]) {
+        for element in elements.iter_mut() {
+            element.0 = reduce(&element.0);
+        }
+    }
+
+    /// Inverts `self` with the inversion syscall directly, skipping the zero
+    /// check of `invert`. The result for zero input is unspecified, so only
+    /// call this where the input is already known to be nonzero.
+    pub fn invert_unchecked(&self) -> Fr {
+        debug_assert!(self.is_canonical());
+        #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
+        {
+            let mut tmp = Self::zero();
+            unsafe {
+                sp1_intrinsics::bn254::syscall_bn254_scalar_inv(&mut tmp.0, &self.0);
+            }
+            tmp
+        }
+        #[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+        {
+            arithmetic::invert(self).unwrap_or(Self::zero())
+        }
+    }
+
     #[inline]
     pub fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
         Fr([
@@ -71,6 +168,41 @@ impl Fr {
             u64::conditional_select(&a.0[3], &b.0[3], choice),
         ])
     }
+
+    /// Assigns `other` to `self` if `choice` is set, in constant time.
+    #[inline]
+    pub fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        self.0[0].conditional_assign(&other.0[0], choice);
+        self.0[1].conditional_assign(&other.0[1], choice);
+        self.0[2].conditional_assign(&other.0[2], choice);
+        self.0[3].conditional_assign(&other.0[3], choice);
+    }
+}
+
+/// Limbs are canonical here, so the bit is the low limb; this does not branch.
+impl From<bool> for Fr {
+    fn from(bit: bool) -> Fr {
+        Fr([bit as u64, 0, 0, 0])
+    }
+}
+
+/// Every `u64` is below the modulus, so it is already a canonical low limb.
+impl From<u64> for Fr {
+    fn from(v: u64) -> Fr {
+        Fr([v, 0, 0, 0])
+    }
+}
+
+impl From<Fr> for [u8; 32] {
+    fn from(value: Fr) -> [u8; 32] {
+        value.to_repr()
+    }
+}
+
+impl<'a> From<&'a Fr> for [u8; 32] {
+    fn from(value: &'a Fr) -> [u8; 32] {
+        value.to_repr()
+    }
 }
 
 impl Add<&Fr> for &Fr {
@@ -78,6 +210,7 @@ impl Add<&Fr> for &Fr {
 
     #[inline]
     fn add(self, rhs: &Fr) -> Fr {
+        debug_assert!(self.is_canonical() && rhs.is_canonical());
         let mut tmp = Fr::zero();
         #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
         unsafe {
@@ -95,43 +228,12 @@ impl Add<&Fr> for &Fr {
     }
 }
 
-impl Add<Fr> for Fr {
-    type Output = Fr;
-
-    #[inline]
-    fn add(self, rhs: Fr) -> Fr {
-        &self + &rhs
-    }
-}
-
-impl Add<&Fr> for Fr {
-    type Output = Fr;
-
-    #[inline]
-    fn add(self, rhs: &Fr) -> Fr {
-        &self + rhs
-    }
-}
-
-impl AddAssign<Fr> for Fr {
-    #[inline]
-    fn add_assign(&mut self, rhs: Fr) {
-        *self = &*self + &rhs;
-    }
-}
-
-impl AddAssign<&Fr> for Fr {
-    #[inline]
-    fn add_assign(&mut self, rhs: &Fr) {
-        *self = &*self + rhs;
-    }
-}
-
 impl Sub<&Fr> for &Fr {
     type Output = Fr;
 
     #[inline]
     fn sub(self, rhs: &Fr) -> Fr {
+        debug_assert!(self.is_canonical() && rhs.is_canonical());
         let mut tmp = Fr::zero();
         #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
         unsafe {
@@ -149,43 +251,14 @@ impl Sub<&Fr> for &Fr {
     }
 }
 
-impl Sub<Fr> for Fr {
-    type Output = Fr;
-
-    #[inline]
-    fn sub(self, rhs: Fr) -> Fr {
-        &self - &rhs
-    }
-}
-
-impl Sub<&Fr> for Fr {
-    type Output = Fr;
-
-    #[inline]
-    fn sub(self, rhs: &Fr) -> Fr {
-        &self - rhs
-    }
-}
-
-impl SubAssign<Fr> for Fr {
-    #[inline]
-    fn sub_assign(&mut self, rhs: Fr) {
-        *self = &*self - &rhs;
-    }
-}
-
-impl SubAssign<&Fr> for Fr {
-    #[inline]
-    fn sub_assign(&mut self, rhs: &Fr) {
-        *self = &*self - rhs;
-    }
-}
+impl_binops_additive!(Fr, Fr);
 
 impl Mul<&Fr> for &Fr {
     type Output = Fr;
 
     #[inline]
     fn mul(self, rhs: &Fr) -> Fr {
+        debug_assert!(self.is_canonical() && rhs.is_canonical());
         let mut tmp = Fr::zero();
         #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
         unsafe {
@@ -221,25 +294,14 @@ impl Mul<&Fr> for Fr {
     }
 }
 
-impl MulAssign<Fr> for Fr {
-    #[inline]
-    fn mul_assign(&mut self, rhs: Fr) {
-        *self = &*self * &rhs;
-    }
-}
-
-impl MulAssign<&Fr> for Fr {
-    #[inline]
-    fn mul_assign(&mut self, rhs: &Fr) {
-        *self = &*self * rhs;
-    }
-}
+impl_mul_assign!(Fr, Fr);
 
 impl Neg for &Fr {
     type Output = Fr;
 
     #[inline]
     fn neg(self) -> Fr {
+        debug_assert!(self.is_canonical());
         let mut tmp = Fr::zero();
         #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
         unsafe {
@@ -280,6 +342,7 @@ impl Field for Fr {
     }
 
     fn square(&self) -> Self {
+        debug_assert!(self.is_canonical());
         let mut tmp = Self::zero();
         #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
         unsafe {
@@ -300,6 +363,7 @@ impl Field for Fr {
     }
 
     fn invert(&self) -> CtOption<Self> {
+        debug_assert!(self.is_canonical());
         let mut tmp = Self::zero();
         #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
         unsafe {
@@ -315,8 +379,14 @@ impl Field for Fr {
         CtOption::new(tmp, !self.ct_eq(&Self::zero()))
     }
 
-    fn sqrt_ratio(_: &Self, _: &Self) -> (Choice, Self) {
-        (Choice::from(1u8), Self::one())
+    // `sqrt_ratio_generic` calls `sqrt`, so `sqrt` must not fall back to the
+    // trait default, which calls `sqrt_ratio`.
+    fn sqrt(&self) -> CtOption<Self> {
+        fr_common::sqrt(self)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        fr_common::sqrt_ratio(num, div)
     }
 }
 
@@ -326,7 +396,27 @@ impl PrimeField for Fr {
     const MODULUS: &'static str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
     const NUM_BITS: u32 = 254;
     const CAPACITY: u32 = 253;
-    const TWO_INV: Self = Fr([0x7f80000000000001, 0xb784000000000001, 0x0, 0x0]);
+    const TWO_INV: Self = Fr(fr_common::TWO_INV_LIMBS);
+    const MULTIPLICATIVE_GENERATOR: Self = Fr([0x07, 0x00, 0x00, 0x00]);
+    const S: u32 = 28;
+    const ROOT_OF_UNITY: Self = Fr([
+        0xd34f1ed960c37c9c,
+        0x3215cf6dd39329c8,
+        0x98865ea93dd31f74,
+        0x03ddb9f5166d18b7,
+    ]);
+    const ROOT_OF_UNITY_INV: Self = Fr([
+        0x0ed3e50a414e6dba,
+        0xb22625f59115aba7,
+        0x1bbe587180f34361,
+        0x048127174daabc26,
+    ]);
+    const DELTA: Self = Fr([
+        0x870e56bbe533e9a2,
+        0x5b5f898e5e963f25,
+        0x64ec26aad4c86e71,
+        0x09226b6e22c6f0ca,
+    ]);
     
     fn from_repr(repr: Self::Repr) -> CtOption<Self> {
         Self::from_bytes(&repr)
@@ -343,12 +433,67 @@ impl PrimeField for Fr {
     fn is_odd(&self) -> Choice {
         Choice::from((self.0[0] & 1) as u8)
     }
+
+    /// Writes the value straight into the two low limbs; `2^128 < MODULUS`,
+    /// so the result is already canonical.
+    fn from_u128(v: u128) -> Self {
+        Fr([v as u64, (v >> 64) as u64, 0, 0])
+    }
+}
+
+#[cfg(all(feature = "bits", target_pointer_width = "64"))]
+impl PrimeFieldBits for Fr {
+    type ReprBits = [u64; 4];
+
+    fn to_le_bits(&self) -> FieldBits<Self::ReprBits> {
+        let bytes = self.to_repr();
+        FieldBits::new(
+            [0, 8, 16, 24].map(|i| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap())),
+        )
+    }
+
+    fn char_le_bits() -> FieldBits<Self::ReprBits> {
+        FieldBits::new(MODULUS)
+    }
+}
+
+#[cfg(all(feature = "bits", not(target_pointer_width = "64")))]
+impl PrimeFieldBits for Fr {
+    type ReprBits = [u32; 8];
+
+    fn to_le_bits(&self) -> FieldBits<Self::ReprBits> {
+        let bytes = self.to_repr();
+        FieldBits::new(
+            [0, 4, 8, 12, 16, 20, 24, 28]
+                .map(|i| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap())),
+        )
+    }
+
+    fn char_le_bits() -> FieldBits<Self::ReprBits> {
+        FieldBits::new(MODULUS_LIMBS_32)
+    }
 }
 
 impl ConditionallySelectable for Fr {
     fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
         Fr::conditional_select(a, b, choice)
     }
+
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        Fr::conditional_assign(self, other, choice)
+    }
+}
+
+/// `from_raw` and the public tuple field accept non-canonical limbs, so
+/// equality compares the residues rather than the raw limbs: `Fr(MODULUS)`
+/// equals `Fr::zero()`. Both sides are reduced with a fixed sequence of masked
+/// subtractions, so the comparison itself is constant time, but it is several
+/// times more expensive than `ct_eq`, which compares raw limbs and is only
+/// meaningful for canonical values.
+impl PartialEq for Fr {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(reduce(&self.0).ct_eq(&reduce(&other.0)))
+    }
 }
 
 impl ConstantTimeEq for Fr {
@@ -410,4 +555,106 @@ mod tests {
         let e = -&d;
         assert_ne!(e, d);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_eq_non_canonical() {
+        let five = Fr::from_raw([5, 0, 0, 0]);
+        let five_plus_modulus =
+            Fr::from_raw([MODULUS[0] + 5, MODULUS[1], MODULUS[2], MODULUS[3]]);
+        assert_eq!(five, five_plus_modulus);
+        assert_eq!(Fr::from_raw(MODULUS), Fr::zero());
+        assert_ne!(five_plus_modulus, Fr::from_raw([6, 0, 0, 0]));
+
+        // 2^256 - 1 = 5 * MODULUS + (R - 1)
+        let r_minus_one = [
+            0xac96341c4ffffffa,
+            0x36fc76959f60cd29,
+            0x666ea36f7879462e,
+            0x0e0a77c19a07df2f,
+        ];
+        assert_eq!(reduce(&[u64::MAX; 4]), r_minus_one);
+        assert_eq!(Fr::from_raw([u64::MAX; 4]), Fr::from_raw(r_minus_one));
+    }
+
+    #[test]
+    fn test_invert_unchecked() {
+        for x in [
+            Fr::one(),
+            Fr::from_raw([2, 0, 0, 0]),
+            Fr::from_raw([1, 2, 3, 4]),
+            -Fr::one(),
+        ] {
+            assert_eq!(x.invert_unchecked(), x.invert().unwrap());
+            assert_eq!(x.invert_unchecked() * x, Fr::one());
+        }
+    }
+
+    #[test]
+    fn test_from_u128_limbs() {
+        assert_eq!(Fr::from_u128(u128::MAX).0, [u64::MAX, u64::MAX, 0, 0]);
+        assert_eq!(Fr::from_u128(1 << 64).0, [0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_batch_normalize() {
+        let mut elements = [
+            Fr::from_raw(MODULUS),
+            Fr::from_raw([MODULUS[0] + 5, MODULUS[1], MODULUS[2], MODULUS[3]]),
+            Fr::from_raw([u64::MAX; 4]),
+            Fr::from_raw([7, 0, 0, 0]),
+        ];
+        Fr::batch_normalize(&mut elements);
+
+        assert!(elements.iter().all(Fr::is_canonical));
+        assert_eq!(elements[0].0, [0, 0, 0, 0]);
+        assert_eq!(elements[1].0, [5, 0, 0, 0]);
+        assert_eq!(elements[2].0, reduce(&[u64::MAX; 4]));
+        assert_eq!(elements[3].0, [7, 0, 0, 0]);
+    }
+
+    fn repr_of(limbs: [u64; 4]) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_from_bytes_boundaries() {
+        let m = MODULUS;
+        // MODULUS itself: every limb equal, rejected
+        assert!(bool::from(Fr::from_bytes(&repr_of(m)).is_none()));
+        // MODULUS - 1: accepted
+        let max = Fr::from_bytes(&repr_of([m[0] - 1, m[1], m[2], m[3]])).unwrap();
+        assert_eq!(max, -Fr::one());
+        // 0: accepted
+        assert_eq!(Fr::from_bytes(&[0u8; 32]).unwrap(), Fr::zero());
+        // 2^256 - 1: rejected
+        assert!(bool::from(Fr::from_bytes(&[0xff; 32]).is_none()));
+        // MODULUS + 1, and values that only differ from MODULUS in one limb
+        assert!(bool::from(Fr::from_bytes(&repr_of([m[0] + 1, m[1], m[2], m[3]])).is_none()));
+        assert!(bool::from(Fr::from_bytes(&repr_of([m[0], m[1], m[2] + 1, m[3]])).is_none()));
+        let below = Fr::from_bytes(&repr_of([u64::MAX, m[1], m[2], m[3] - 1]));
+        assert!(bool::from(below.is_some()));
+    }
+
+    #[test]
+    fn test_two_inv() {
+        // (MODULUS + 1) / 2
+        assert_eq!(Fr::TWO_INV.double(), Fr::one());
+        assert_eq!(Fr::TWO_INV + Fr::TWO_INV, Fr::ONE);
+        assert_eq!(Fr::TWO_INV * Fr::from(2u64), Fr::ONE);
+        assert!(Fr::TWO_INV.is_canonical());
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        assert!(Fr::zero().is_canonical());
+        assert!(Fr::one().is_canonical());
+        assert!(Fr::from_raw([MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]).is_canonical());
+        assert!(!Fr::from_raw(MODULUS).is_canonical());
+        assert!(!Fr::from_raw([0, 0, 0, MODULUS[3] + 1]).is_canonical());
+        assert!(!Fr::from_raw([u64::MAX; 4]).is_canonical());
+    }
+}
@@ -0,0 +1,227 @@
+//! Hashing arbitrary byte strings to field elements.
+//!
+//! A message is absorbed into BLAKE2b-512 after a length-prefixed domain
+//! separator, and the 64-byte digest is reduced modulo the field order. The
+//! wide reduction keeps the bias below `2^-258`.
+
+use crate::Fr;
+use blake2::digest::{FixedOutput, Update};
+use blake2::Blake2b512;
+use ff::Field;
+
+/// `(MODULUS - 1) / p` for every prime `p` dividing `MODULUS - 1`, with `p`
+/// noted above each entry. The only repeated factors are `2^28` and `3^2`.
+const ORDER_COFACTORS: [[u64; 4]; 10] = [
+    // 2
+    [
+        0xa1f0fac9f8000000,
+        0x9419f4243cdcb848,
+        0xdc2822db40c0ac2e,
+        0x183227397098d014,
+    ],
+    // 3
+    [
+        0x16a0a73150000000,
+        0xb8114d6d7de87adb,
+        0xe81ac1e7808072c9,
+        0x10216f7ba065e00d,
+    ],
+    // 13
+    [
+        0xddd64df7b0000000,
+        0x658dd6ca7f846b1e,
+        0x70a3b697e293cbb8,
+        0x03b8f2579b2b33b4,
+    ],
+    // 29
+    [
+        0x1cd2d380b0000000,
+        0x6b513cf9abebe966,
+        0xf4b351da27c6aac5,
+        0x01ab2ed7d2ccbee6,
+    ],
+    // 983
+    [
+        0xe8e41a8d90000000,
+        0xfb5a6bcaf2523925,
+        0x6cc921aa814dbd28,
+        0x000c9a40b3ec84b8,
+    ],
+    // 11003
+    [
+        0x77097778d0000000,
+        0xac8c117e818301af,
+        0xe8a8450f9ae5651f,
+        0x0001203b2751402e,
+    ],
+    // 237073
+    [
+        0xe50be724f0000000,
+        0xbfc3050bc4fa06f1,
+        0x5c806fa122d64edd,
+        0x00000d60997189db,
+    ],
+    // 405928799
+    [
+        0x129494e210000000,
+        0x0567610cf77df233,
+        0xc66f4ff1843aa254,
+        0x0000000200039ffc,
+    ],
+    // 1670836401704629
+    [
+        0x5f80f0ca30000000,
+        0x89ae6358fe5bac4c,
+        0xfa48d33ee8001592,
+        0x0000000000000826,
+    ],
+    // 13818364434197438864469338081
+    [
+        0x408cc251f0000000,
+        0x232514fb3c4e3e9c,
+        0x000000011574ddb3,
+        0x0000000000000000,
+    ],
+];
+
+/// Returns whether `x` generates the whole multiplicative group, i.e. whether
+/// `x^((MODULUS - 1) / p) != 1` for every prime `p` dividing `MODULUS - 1`.
+fn is_generator(x: &Fr) -> bool {
+    !bool::from(x.is_zero())
+        && ORDER_COFACTORS
+            .iter()
+            .all(|cofactor| x.pow_vartime(cofactor) != Fr::ONE)
+}
+
+impl Fr {
+    /// Hashes `message` to a field element under `domain_prefix`. This is the
+    /// single-shot form of [`HashToFr`].
+    pub fn hash_to_field(domain_prefix: &[u8], message: &[u8]) -> Fr {
+        let mut hasher = HashToFr::new(domain_prefix);
+        hasher.update(message);
+        hasher.finalize()
+    }
+
+    /// Deterministically derives a generator of the multiplicative group from
+    /// `seed`: candidates `hash(seed || counter)` for `counter = 0, 1, ...`
+    /// are tried until one has full order `MODULUS - 1`.
+    ///
+    /// This runs in variable time and is meant for public parameter setup.
+    pub fn derive_generator(seed: &[u8]) -> Fr {
+        (0u64..)
+            .map(|counter| {
+                let mut hasher = HashToFr::new(b"bn254-derive-generator");
+                hasher.update(seed);
+                hasher.update(&counter.to_le_bytes());
+                hasher.finalize()
+            })
+            .find(is_generator)
+            .unwrap()
+    }
+}
+
+/// Streaming hash-to-field: feed the message with `update` in as many pieces as
+/// convenient, then call `finalize`. The result only depends on the
+/// concatenation of the pieces.
+#[derive(Clone, Debug)]
+pub struct HashToFr {
+    state: Blake2b512,
+}
+
+impl HashToFr {
+    /// Starts hashing a message under `domain_prefix`.
+    pub fn new(domain_prefix: &[u8]) -> Self {
+        let mut state = Blake2b512::default();
+        Update::update(&mut state, &(domain_prefix.len() as u64).to_le_bytes());
+        Update::update(&mut state, domain_prefix);
+        HashToFr { state }
+    }
+
+    /// Absorbs the next piece of the message.
+    pub fn update(&mut self, bytes: &[u8]) {
+        Update::update(&mut self.state, bytes);
+    }
+
+    /// Consumes the hasher and reduces the digest to a field element.
+    pub fn finalize(self) -> Fr {
+        let digest = self.state.finalize_fixed();
+        let mut limbs = [0u64; 8];
+        for (limb, chunk) in limbs.iter_mut().zip(digest.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Fr::from_u64_digits(&limbs)
+    }
+}
+
+impl Update for HashToFr {
+    fn update(&mut self, data: &[u8]) {
+        HashToFr::update(self, data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::PrimeField;
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_streaming_matches_single_shot() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut message = vec![0u8; 1000];
+        rng.fill_bytes(&mut message);
+
+        let expected = Fr::hash_to_field(b"bn254-test", &message);
+        for chunk_size in [1, 7, 64, 128, 333, 1000] {
+            let mut hasher = HashToFr::new(b"bn254-test");
+            for chunk in message.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), expected);
+        }
+
+        // Through the `digest` trait
+        let mut hasher = HashToFr::new(b"bn254-test");
+        Update::update(&mut hasher, &message[..500]);
+        Update::update(&mut hasher, &message[500..]);
+        assert_eq!(hasher.finalize(), expected);
+    }
+
+    #[test]
+    fn test_is_generator() {
+        assert!(is_generator(&Fr::MULTIPLICATIVE_GENERATOR));
+        assert!(!is_generator(&Fr::ZERO));
+        assert!(!is_generator(&Fr::ONE));
+        assert!(!is_generator(&Fr::ROOT_OF_UNITY));
+        assert!(!is_generator(&Fr::MULTIPLICATIVE_GENERATOR.square()));
+        assert!(!is_generator(
+            &Fr::MULTIPLICATIVE_GENERATOR.pow_vartime([13])
+        ));
+    }
+
+    #[test]
+    fn test_derive_generator() {
+        let g = Fr::derive_generator(b"seed");
+        assert!(is_generator(&g));
+        assert_eq!(g, Fr::derive_generator(b"seed"));
+        assert_ne!(g, Fr::derive_generator(b"other seed"));
+
+        // Full order: g^((MODULUS - 1) / 2) is -1 and g^(MODULUS - 1) is 1.
+        let m = Fr::modulus_limbs();
+        assert_eq!(g.pow_vartime(ORDER_COFACTORS[0]), -Fr::ONE);
+        assert!(g.has_order_dividing(&[m[0] - 1, m[1], m[2], m[3]]));
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        assert_ne!(
+            Fr::hash_to_field(b"a", b"bc"),
+            Fr::hash_to_field(b"ab", b"c")
+        );
+        assert_ne!(
+            Fr::hash_to_field(b"bn254-test", b""),
+            Fr::hash_to_field(b"bn254-other", b"")
+        );
+    }
+}
@@ -2,12 +2,19 @@
 #![allow(unexpected_cfgs)]
 
 #[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
-mod arithmetic;
+pub mod arithmetic;
+#[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+mod bernstein_yang;
 
 #[cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
 mod fr;
 #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))]
 mod fr_sp1;
+mod fr_common;
+
+pub use fr_common::Boundary;
+#[cfg(feature = "rkyv")]
+pub use fr_common::ArchivedFr;
 
 #[cfg(feature = "asm")]
 mod assembly;
@@ -15,6 +22,9 @@ mod assembly;
 #[macro_use]
 mod derive;
 
+#[cfg(feature = "hash")]
+pub mod hash;
+pub mod poly;
 pub mod serde;
 
 // Re-export ff and group to simplify down stream dependencies
@@ -0,0 +1,112 @@
+use crate::Fr;
+use core::ops::Add;
+use ff::Field;
+
+/// A polynomial over `Fr` in coefficient form, lowest degree first. The zero
+/// polynomial may be represented by any number of zero coefficients.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial(pub Vec<Fr>);
+
+impl Polynomial {
+    /// Evaluates the polynomial at `x` using Horner's rule.
+    pub fn eval(&self, x: &Fr) -> Fr {
+        self.0.iter().rev().fold(Fr::ZERO, |acc, c| acc * x + c)
+    }
+
+    /// Multiplies every coefficient by `scalar`.
+    pub fn scale(&self, scalar: &Fr) -> Polynomial {
+        self.0.iter().map(|c| c * scalar).collect()
+    }
+}
+
+impl<'a, 'b> Add<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: &'b Polynomial) -> Polynomial {
+        let (long, short) = if self.0.len() >= rhs.0.len() {
+            (&self.0, &rhs.0)
+        } else {
+            (&rhs.0, &self.0)
+        };
+        let mut res = long.clone();
+        for (r, c) in res.iter_mut().zip(short.iter()) {
+            *r += c;
+        }
+        Polynomial(res)
+    }
+}
+
+impl Add<Polynomial> for Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: Polynomial) -> Polynomial {
+        &self + &rhs
+    }
+}
+
+impl FromIterator<Fr> for Polynomial {
+    fn from_iter<I: IntoIterator<Item = Fr>>(iter: I) -> Self {
+        Polynomial(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Polynomial {
+    type Item = Fr;
+    type IntoIter = std::vec::IntoIter<Fr>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Polynomial {
+    type Item = &'a Fr;
+    type IntoIter = core::slice::Iter<'a, Fr>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(coeffs: &[u64]) -> Polynomial {
+        coeffs.iter().map(|&c| Fr::from(c)).collect()
+    }
+
+    #[test]
+    fn test_eval() {
+        // 3 + 2x + x^2
+        let p = poly(&[3, 2, 1]);
+        assert_eq!(p.eval(&Fr::ZERO), Fr::from(3u64));
+        assert_eq!(p.eval(&Fr::from(5u64)), Fr::from(38u64));
+        assert_eq!(p.eval(&-Fr::ONE), Fr::from(2u64));
+        assert_eq!(Polynomial::default().eval(&Fr::from(5u64)), Fr::ZERO);
+        assert_eq!(
+            p.scale(&Fr::from(2u64)).eval(&Fr::from(5u64)),
+            Fr::from(76u64)
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let p = poly(&[3, 2, 1]);
+        let q = poly(&[1, 4]);
+        assert_eq!(&p + &q, poly(&[4, 6, 1]));
+        assert_eq!(&q + &p, poly(&[4, 6, 1]));
+        assert_eq!(p.clone() + Polynomial::default(), p);
+
+        let x = Fr::from(7u64);
+        assert_eq!((&p + &q).eval(&x), p.eval(&x) + q.eval(&x));
+    }
+
+    #[test]
+    fn test_iter() {
+        let p = poly(&[3, 2, 1]);
+        let doubled: Polynomial = (&p).into_iter().map(|c| c.double()).collect();
+        assert_eq!(doubled, poly(&[6, 4, 2]));
+        assert_eq!(p.into_iter().count(), 3);
+    }
+}
@@ -1,4 +1,54 @@
+use std::fmt;
 use std::io::{self, Read, Write};
+use std::ops::{Index, IndexMut};
+
+use crate::Fr;
+use ff::PrimeField;
+
+/// Reasons a raw encoding can fail to decode.
+#[derive(Debug)]
+pub enum SerdeError {
+    /// The reader ran out of bytes before a whole value was read.
+    UnexpectedEof,
+    /// The input slice does not have the encoded length of the type.
+    WrongLength { expected: usize, actual: usize },
+    /// The decoded integer is not less than the field modulus.
+    NonCanonical,
+    /// Any other I/O failure of the underlying reader.
+    Io(io::Error),
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerdeError::UnexpectedEof => write!(f, "unexpected end of input"),
+            SerdeError::WrongLength { expected, actual } => {
+                write!(f, "expected {} bytes, got {}", expected, actual)
+            }
+            SerdeError::NonCanonical => write!(f, "input number is not less than field modulus"),
+            SerdeError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SerdeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SerdeError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SerdeError {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            SerdeError::UnexpectedEof
+        } else {
+            SerdeError::Io(err)
+        }
+    }
+}
 
 /// Trait for converting raw bytes to/from the internal representation of a type.
 /// For example, field elements are represented in Montgomery form and serialized/deserialized without Montgomery reduction.
@@ -9,8 +59,10 @@ pub trait SerdeObject: Sized {
     /// used internally as an extension of machine memory. It should not be used to deserialize
     /// externally provided data.
     fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self;
-    fn from_raw_bytes(bytes: &[u8]) -> Option<Self>;
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, SerdeError>;
 
+    /// Encodes the internal limbs least significant first, each limb in
+    /// little-endian byte order, so the whole buffer is a little-endian integer.
     fn to_raw_bytes(&self) -> Vec<u8>;
 
     /// The purpose of unchecked functions is to read the internal memory representation
@@ -19,11 +71,47 @@ pub trait SerdeObject: Sized {
     /// internally when some machine state cannot be kept in memory (e.g., between runs)
     /// and needs to be reloaded as quickly as possible.
     fn read_raw_unchecked<R: Read>(reader: &mut R) -> Self;
-    fn read_raw<R: Read>(reader: &mut R) -> io::Result<Self>;
+    fn read_raw<R: Read>(reader: &mut R) -> Result<Self, SerdeError>;
+
+    /// Reads the next object from `reader` into `self`, with the same checks as
+    /// `read_raw`, so a loop can reuse one scratch value. On error `self` is
+    /// left unchanged. Errors are reported as `SerdeError`, like `read_raw`.
+    fn read_raw_into<R: Read>(&mut self, reader: &mut R) -> Result<(), SerdeError> {
+        *self = Self::read_raw(reader)?;
+        Ok(())
+    }
 
     fn write_raw<W: Write>(&self, writer: &mut W) -> io::Result<()>;
 }
 
+/// Raw-byte encodings with an explicit byte order. The little-endian methods
+/// are the `SerdeObject` layout; the big-endian ones reverse the whole buffer,
+/// which matches tools that write the internal representation as one
+/// big-endian integer.
+pub trait SerdeObjectEndian: SerdeObject {
+    fn from_raw_bytes_le(bytes: &[u8]) -> Result<Self, SerdeError> {
+        Self::from_raw_bytes(bytes)
+    }
+
+    fn from_raw_bytes_be(bytes: &[u8]) -> Result<Self, SerdeError> {
+        let mut le = bytes.to_vec();
+        le.reverse();
+        Self::from_raw_bytes(&le)
+    }
+
+    fn to_raw_bytes_le(&self) -> Vec<u8> {
+        self.to_raw_bytes()
+    }
+
+    fn to_raw_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_raw_bytes();
+        bytes.reverse();
+        bytes
+    }
+}
+
+impl<T: SerdeObject> SerdeObjectEndian for T {}
+
 impl SerdeObject for crate::Fr {
     fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
         let mut tmp = [0u64; 4];
@@ -34,11 +122,19 @@ impl SerdeObject for crate::Fr {
         Self(tmp)
     }
 
-    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, SerdeError> {
         if bytes.len() != 32 {
-            return None;
+            return Err(SerdeError::WrongLength {
+                expected: 32,
+                actual: bytes.len(),
+            });
+        }
+        let elt = Self::from_raw_bytes_unchecked(bytes);
+        if elt.is_canonical() {
+            Ok(elt)
+        } else {
+            Err(SerdeError::NonCanonical)
         }
-        Some(Self::from_raw_bytes_unchecked(bytes))
     }
 
     fn to_raw_bytes(&self) -> Vec<u8> {
@@ -55,10 +151,10 @@ impl SerdeObject for crate::Fr {
         Self::from_raw_bytes_unchecked(&bytes)
     }
 
-    fn read_raw<R: Read>(reader: &mut R) -> io::Result<Self> {
+    fn read_raw<R: Read>(reader: &mut R) -> Result<Self, SerdeError> {
         let mut bytes = [0u8; 32];
         reader.read_exact(&mut bytes)?;
-        Ok(Self::from_raw_bytes_unchecked(&bytes))
+        Self::from_raw_bytes(&bytes)
     }
 
     fn write_raw<W: Write>(&self, writer: &mut W) -> io::Result<()> {
@@ -66,6 +162,320 @@ impl SerdeObject for crate::Fr {
     }
 }
 
+/// A fixed-size array of field elements whose raw encoding is the raw
+/// encodings of its elements back to back, exactly `32 * N` bytes with no
+/// length prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrArray<const N: usize>(pub [crate::Fr; N]);
+
+impl<const N: usize> Index<usize> for FrArray<N> {
+    type Output = crate::Fr;
+
+    fn index(&self, index: usize) -> &crate::Fr {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for FrArray<N> {
+    fn index_mut(&mut self, index: usize) -> &mut crate::Fr {
+        &mut self.0[index]
+    }
+}
+
+impl<const N: usize> SerdeObject for FrArray<N> {
+    fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+        let mut elements = [<crate::Fr as ff::Field>::ZERO; N];
+        for (elt, chunk) in elements.iter_mut().zip(bytes.chunks(32)) {
+            *elt = crate::Fr::from_raw_bytes_unchecked(chunk);
+        }
+        FrArray(elements)
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, SerdeError> {
+        if bytes.len() != 32 * N {
+            return Err(SerdeError::WrongLength {
+                expected: 32 * N,
+                actual: bytes.len(),
+            });
+        }
+        let mut elements = [<crate::Fr as ff::Field>::ZERO; N];
+        for (elt, chunk) in elements.iter_mut().zip(bytes.chunks_exact(32)) {
+            *elt = crate::Fr::from_raw_bytes(chunk)?;
+        }
+        Ok(FrArray(elements))
+    }
+
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(32 * N);
+        for elt in self.0.iter() {
+            bytes.extend_from_slice(&elt.to_raw_bytes());
+        }
+        bytes
+    }
+
+    fn read_raw_unchecked<R: Read>(reader: &mut R) -> Self {
+        let mut elements = [<crate::Fr as ff::Field>::ZERO; N];
+        for elt in elements.iter_mut() {
+            *elt = crate::Fr::read_raw_unchecked(reader);
+        }
+        FrArray(elements)
+    }
+
+    fn read_raw<R: Read>(reader: &mut R) -> Result<Self, SerdeError> {
+        let mut elements = [<crate::Fr as ff::Field>::ZERO; N];
+        for elt in elements.iter_mut() {
+            *elt = crate::Fr::read_raw(reader)?;
+        }
+        Ok(FrArray(elements))
+    }
+
+    fn write_raw<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for elt in self.0.iter() {
+            elt.write_raw(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes an optional field element as a presence byte (`0` for `None`, `1`
+/// for `Some`) followed, when present, by the 32-byte canonical encoding from
+/// `PrimeField::to_repr`.
+pub fn write_opt_fr<W: Write>(value: Option<&crate::Fr>, writer: &mut W) -> io::Result<()> {
+    match value {
+        None => writer.write_all(&[0]),
+        Some(fr) => {
+            writer.write_all(&[1])?;
+            writer.write_all(&ff::PrimeField::to_repr(fr))
+        }
+    }
+}
+
+/// Reads an optional field element written by [`write_opt_fr`]. Fails with
+/// `InvalidData` on an unknown presence byte or a non-canonical encoding.
+pub fn read_opt_fr<R: Read>(reader: &mut R) -> io::Result<Option<crate::Fr>> {
+    let mut flag = [0u8; 1];
+    reader.read_exact(&mut flag)?;
+    match flag[0] {
+        0 => Ok(None),
+        1 => {
+            let mut repr = [0u8; 32];
+            reader.read_exact(&mut repr)?;
+            Option::from(<crate::Fr as ff::PrimeField>::from_repr(repr))
+                .map(Some)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "input number is not less than field modulus",
+                    )
+                })
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid presence flag for optional field element",
+        )),
+    }
+}
+
+/// `R = 2^256 mod r`, the Montgomery radix as a field element.
+const MONTGOMERY_R: Fr = Fr::from_raw([
+    0xac96341c4ffffffb,
+    0x36fc76959f60cd29,
+    0x666ea36f7879462e,
+    0x0e0a77c19a07df2f,
+]);
+
+/// `R^{-1} mod r`
+const MONTGOMERY_R_INV: Fr = Fr::from_raw([
+    0xdc5ba0056db1194e,
+    0x090ef5a9e111ec87,
+    0xc8260de4aeb85d5d,
+    0x15ebf95182c5551c,
+]);
+
+impl Fr {
+    /// Decodes the 32-byte little-endian encoding of a Montgomery-form value
+    /// `aR mod r`, as written by halo2curves-style producers, into `a`.
+    pub fn from_montgomery_bytes(bytes: &[u8; 32]) -> Result<Fr, SerdeError> {
+        Option::from(Fr::from_repr(*bytes))
+            .map(|mont: Fr| mont * MONTGOMERY_R_INV)
+            .ok_or(SerdeError::NonCanonical)
+    }
+
+    /// Encodes `self` as the 32-byte little-endian encoding of its
+    /// Montgomery form `aR mod r`. Inverse of `from_montgomery_bytes`.
+    pub fn to_montgomery_bytes(&self) -> [u8; 32] {
+        (self * MONTGOMERY_R).to_repr()
+    }
+
+    /// Decodes consecutive 32-byte Montgomery-form encodings from `input` into
+    /// `out`, one element per chunk, as `from_montgomery_bytes` does.
+    ///
+    /// Fails with `WrongLength` unless `input` is exactly `32 * out.len()`
+    /// bytes, and with `NonCanonical` at the first chunk that is not less
+    /// than the modulus; `out` may then be partially overwritten.
+    pub fn from_montgomery_bytes_batch(input: &[u8], out: &mut [Fr]) -> Result<(), SerdeError> {
+        if input.len() != 32 * out.len() {
+            return Err(SerdeError::WrongLength {
+                expected: 32 * out.len(),
+                actual: input.len(),
+            });
+        }
+        for (elt, chunk) in out.iter_mut().zip(input.chunks_exact(32)) {
+            *elt = Fr::from_montgomery_bytes(chunk.try_into().unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+/// An `Fr` that serializes as the 32-byte little-endian encoding of its
+/// canonical integer value, i.e. the same bytes as `PrimeField::to_repr`.
+/// Deserialization rejects encodings that are not less than the modulus.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CanonicalFr(pub Fr);
+
+/// An `Fr` that serializes as the 32-byte little-endian encoding of its
+/// Montgomery form `aR mod r` with `R = 2^256`, the layout used by
+/// halo2curves-style producers. Deserialization rejects encodings that are
+/// not less than the modulus.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MontgomeryFr(pub Fr);
+
+#[cfg(feature = "serde")]
+impl From<Fr> for CanonicalFr {
+    fn from(value: Fr) -> Self {
+        CanonicalFr(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<CanonicalFr> for Fr {
+    fn from(value: CanonicalFr) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Fr> for MontgomeryFr {
+    fn from(value: Fr) -> Self {
+        MontgomeryFr(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<MontgomeryFr> for Fr {
+    fn from(value: MontgomeryFr) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for CanonicalFr {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ::serde::Serialize::serialize(&self.0.to_repr(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for CanonicalFr {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = <[u8; 32] as ::serde::Deserialize>::deserialize(deserializer)?;
+        Option::from(Fr::from_repr(bytes))
+            .map(CanonicalFr)
+            .ok_or_else(|| {
+                ::serde::de::Error::custom("input number is not less than field modulus")
+            })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for MontgomeryFr {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ::serde::Serialize::serialize(&self.0.to_montgomery_bytes(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for MontgomeryFr {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = <[u8; 32] as ::serde::Deserialize>::deserialize(deserializer)?;
+        Fr::from_montgomery_bytes(&bytes)
+            .map(MontgomeryFr)
+            .map_err(::serde::de::Error::custom)
+    }
+}
+
+/// Tag identifying this field in the JSON form written by `Fr::to_json_value`.
+#[cfg(feature = "json")]
+pub const JSON_FIELD_TAG: &str = "bn254-fr";
+
+/// Reasons `Fr::from_json_value` can reject a JSON value.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// The `field` tag names a different field than `JSON_FIELD_TAG`.
+    UnknownField(String),
+    /// The value is not an object with string `field` and `value` members,
+    /// or `value` is not `0x` followed by 64 hex digits.
+    Malformed,
+    /// The encoded integer is not less than the field modulus.
+    NonCanonical,
+}
+
+#[cfg(feature = "json")]
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::UnknownField(tag) => write!(f, "unknown field tag {:?}", tag),
+            JsonError::Malformed => write!(f, "malformed field element JSON"),
+            JsonError::NonCanonical => write!(f, "input number is not less than field modulus"),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::error::Error for JsonError {}
+
+#[cfg(feature = "json")]
+impl Fr {
+    /// Returns the self-describing JSON form
+    /// `{"field":"bn254-fr","value":"0x..."}`, where `value` is the canonical
+    /// integer as exactly 64 lowercase big-endian hex digits. This schema is
+    /// stable and independent of the `serde` impls.
+    pub fn to_json_value(&self) -> serde_json::Value {
+        let mut hex = String::with_capacity(66);
+        hex.push_str("0x");
+        for byte in self.to_repr().iter().rev() {
+            hex.push_str(&format!("{:02x}", byte));
+        }
+        serde_json::json!({ "field": JSON_FIELD_TAG, "value": hex })
+    }
+
+    /// Parses the JSON form written by `to_json_value`. Hex digits may be in
+    /// either case. Rejects other field tags, so elements of a different field
+    /// are not silently reinterpreted.
+    pub fn from_json_value(value: &serde_json::Value) -> Result<Fr, JsonError> {
+        let field = value.get("field").and_then(|v| v.as_str()).ok_or(JsonError::Malformed)?;
+        if field != JSON_FIELD_TAG {
+            return Err(JsonError::UnknownField(field.to_string()));
+        }
+        let hex = value
+            .get("value")
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.strip_prefix("0x"))
+            .filter(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or(JsonError::Malformed)?;
+
+        let mut repr = [0u8; 32];
+        for (byte, digits) in repr.iter_mut().rev().zip(hex.as_bytes().chunks_exact(2)) {
+            // Checked to be ASCII hex digits above.
+            *byte = u8::from_str_radix(std::str::from_utf8(digits).unwrap(), 16).unwrap();
+        }
+        Option::from(Fr::from_repr(repr)).ok_or(JsonError::NonCanonical)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,8 +489,302 @@ mod tests {
         assert_eq!(fr, fr2);
     }
 
+    #[test]
+    fn test_serde_endian_roundtrip() {
+        let fr = Fr::from_raw([1, 2, 3, 4]);
+
+        let le = fr.to_raw_bytes_le();
+        assert_eq!(le, fr.to_raw_bytes());
+        assert_eq!(Fr::from_raw_bytes_le(&le).unwrap(), fr);
+
+        let be = fr.to_raw_bytes_be();
+        assert_eq!(be.iter().rev().copied().collect::<Vec<_>>(), le);
+        assert_eq!(Fr::from_raw_bytes_be(&be).unwrap(), fr);
+
+        assert!(Fr::from_raw_bytes_be(&[0; 31]).is_err());
+    }
+
+    fn fr_array_roundtrip<const N: usize>() {
+        let arr = FrArray::<N>(core::array::from_fn(|i| Fr::from_raw([i as u64 + 1, 2, 3, 4])));
+        let bytes = arr.to_raw_bytes();
+        assert_eq!(bytes.len(), 32 * N);
+        assert_eq!(FrArray::<N>::from_raw_bytes(&bytes).unwrap(), arr);
+        assert_eq!(FrArray::<N>::from_raw_bytes_unchecked(&bytes), arr);
+
+        let mut buf = Vec::new();
+        arr.write_raw(&mut buf).unwrap();
+        assert_eq!(buf, bytes);
+        assert_eq!(FrArray::<N>::read_raw(&mut buf.as_slice()).unwrap(), arr);
+        assert_eq!(FrArray::<N>::read_raw_unchecked(&mut buf.as_slice()), arr);
+
+        assert!(matches!(
+            FrArray::<N>::from_raw_bytes(&[0; 33]),
+            Err(SerdeError::WrongLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fr_array_roundtrip() {
+        fr_array_roundtrip::<0>();
+        fr_array_roundtrip::<1>();
+        fr_array_roundtrip::<8>();
+    }
+
+    #[test]
+    fn test_fr_array_index() {
+        let mut arr = FrArray([Fr::from_raw([1, 0, 0, 0]), Fr::from_raw([2, 0, 0, 0])]);
+        assert_eq!(arr[1], Fr::from_raw([2, 0, 0, 0]));
+        arr[0] = arr[1];
+        assert_eq!(arr.0, [Fr::from_raw([2, 0, 0, 0]); 2]);
+
+        let mut bytes = arr.to_raw_bytes();
+        bytes[32..].copy_from_slice(&[0xff; 32]);
+        assert!(matches!(
+            FrArray::<2>::from_raw_bytes(&bytes),
+            Err(SerdeError::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn test_opt_fr_roundtrip() {
+        let fr = Fr::from_raw([1, 2, 3, 4]);
+
+        let mut buf = Vec::new();
+        write_opt_fr(Some(&fr), &mut buf).unwrap();
+        assert_eq!(buf.len(), 33);
+        assert_eq!(read_opt_fr(&mut buf.as_slice()).unwrap(), Some(fr));
+
+        let mut buf = Vec::new();
+        write_opt_fr(None, &mut buf).unwrap();
+        assert_eq!(buf, [0]);
+        assert_eq!(read_opt_fr(&mut buf.as_slice()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_opt_fr_rejects_invalid() {
+        assert!(read_opt_fr(&mut [2u8].as_slice()).is_err());
+        assert!(read_opt_fr(&mut [1u8; 20].as_slice()).is_err());
+
+        let mut buf = vec![1u8];
+        buf.extend_from_slice(&[0xff; 32]);
+        assert!(read_opt_fr(&mut buf.as_slice()).is_err());
+    }
+
     #[test]
     fn test_invalid_bytes() {
-        assert!(Fr::from_raw_bytes(&[0; 31]).is_none());
+        assert!(matches!(
+            Fr::from_raw_bytes(&[0; 31]),
+            Err(SerdeError::WrongLength {
+                expected: 32,
+                actual: 31
+            })
+        ));
+        assert!(matches!(
+            Fr::from_raw_bytes(&[0xff; 32]),
+            Err(SerdeError::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn test_read_raw_errors() {
+        assert!(matches!(
+            Fr::read_raw(&mut [0u8; 20].as_slice()),
+            Err(SerdeError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            Fr::read_raw(&mut [0xffu8; 32].as_slice()),
+            Err(SerdeError::NonCanonical)
+        ));
+
+        struct FailingReader;
+        impl Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+            }
+        }
+        match Fr::read_raw(&mut FailingReader) {
+            Err(SerdeError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::PermissionDenied),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let fr = Fr::from_raw([1, 2, 3, 4]);
+        let mut buf = Vec::new();
+        fr.write_raw(&mut buf).unwrap();
+        assert_eq!(Fr::read_raw(&mut buf.as_slice()).unwrap(), fr);
+    }
+
+    #[test]
+    fn test_read_raw_into() {
+        let elements: Vec<Fr> = (1..=100u64)
+            .map(|i| Fr::from_raw([i, 2 * i, 3 * i, i]))
+            .collect();
+        let mut buf = Vec::new();
+        for fr in &elements {
+            fr.write_raw(&mut buf).unwrap();
+        }
+
+        let mut reader = buf.as_slice();
+        let mut scratch = Fr::zero();
+        for fr in &elements {
+            scratch.read_raw_into(&mut reader).unwrap();
+            assert_eq!(scratch, *fr);
+        }
+
+        let mut reader = buf.as_slice();
+        for _ in &elements {
+            let mut into = Fr::zero();
+            let mut copy = reader;
+            into.read_raw_into(&mut reader).unwrap();
+            assert_eq!(into, Fr::read_raw(&mut copy).unwrap());
+        }
+
+        // A failed read leaves the target untouched.
+        assert!(matches!(
+            scratch.read_raw_into(&mut reader),
+            Err(SerdeError::UnexpectedEof)
+        ));
+        assert_eq!(scratch, elements[99]);
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_canonical_serde_roundtrip() {
+        let fr = Fr::from(0x1234_5678u64);
+        let json = serde_json::to_string(&CanonicalFr(fr)).unwrap();
+        let back: CanonicalFr = serde_json::from_str(&json).unwrap();
+        assert_eq!(Fr::from(back), fr);
+    }
+
+    #[test]
+    fn test_montgomery_bytes() {
+        let fr = Fr::from(5u64);
+        let bytes = fr.to_montgomery_bytes();
+        assert_eq!(bytes, (fr * MONTGOMERY_R).to_repr());
+        assert_eq!(Fr::from_montgomery_bytes(&bytes).unwrap(), fr);
+        assert_eq!(MONTGOMERY_R * MONTGOMERY_R_INV, Fr::one());
+        // 1 in Montgomery form is R itself.
+        assert_eq!(Fr::one().to_montgomery_bytes(), MONTGOMERY_R.to_repr());
+        assert!(matches!(
+            Fr::from_montgomery_bytes(&[0xff; 32]),
+            Err(SerdeError::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn test_from_montgomery_bytes_batch() {
+        let elements: Vec<Fr> = (0..50u64)
+            .map(|i| Fr::from_raw([i, 2 * i, 3 * i, i]))
+            .collect();
+        let input: Vec<u8> = elements
+            .iter()
+            .flat_map(|fr| fr.to_montgomery_bytes())
+            .collect();
+
+        let mut out = vec![Fr::zero(); elements.len()];
+        Fr::from_montgomery_bytes_batch(&input, &mut out).unwrap();
+        assert_eq!(out, elements);
+        for (fr, chunk) in out.iter().zip(input.chunks_exact(32)) {
+            assert_eq!(*fr, Fr::from_montgomery_bytes(chunk.try_into().unwrap()).unwrap());
+        }
+
+        Fr::from_montgomery_bytes_batch(&[], &mut []).unwrap();
+        assert!(matches!(
+            Fr::from_montgomery_bytes_batch(&input[..32 * 49], &mut out),
+            Err(SerdeError::WrongLength { expected: 1600, actual: 1568 })
+        ));
+        let mut bad = input.clone();
+        bad[32 * 10..32 * 11].copy_from_slice(&[0xff; 32]);
+        assert!(matches!(
+            Fr::from_montgomery_bytes_batch(&bad, &mut out),
+            Err(SerdeError::NonCanonical)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_montgomery_serde_roundtrip() {
+        let fr = Fr::from(0x1234_5678u64);
+        let json = serde_json::to_string(&MontgomeryFr(fr)).unwrap();
+        let back: MontgomeryFr = serde_json::from_str(&json).unwrap();
+        assert_eq!(Fr::from(back), fr);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_encodings_are_distinct() {
+        let fr = Fr::from(5u64);
+        let json = serde_json::to_string(&CanonicalFr(fr)).unwrap();
+        let as_mont: MontgomeryFr = serde_json::from_str(&json).unwrap();
+        assert_ne!(as_mont.0, fr);
+
+        let json = serde_json::to_string(&MontgomeryFr(fr)).unwrap();
+        let as_canonical: CanonicalFr = serde_json::from_str(&json).unwrap();
+        assert_ne!(as_canonical.0, fr);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_non_canonical() {
+        let json = serde_json::to_string(&[0xffu8; 32]).unwrap();
+        assert!(serde_json::from_str::<CanonicalFr>(&json).is_err());
+        assert!(serde_json::from_str::<MontgomeryFr>(&json).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_value_roundtrip() {
+        for fr in [Fr::zero(), Fr::one(), -Fr::one(), Fr::from_raw([1, 2, 3, 4])] {
+            let json = fr.to_json_value();
+            assert_eq!(json["field"], "bn254-fr");
+            assert_eq!(Fr::from_json_value(&json).unwrap(), fr);
+        }
+
+        assert_eq!(
+            Fr::from(0xabcdu64).to_json_value(),
+            serde_json::json!({
+                "field": "bn254-fr",
+                "value": "0x000000000000000000000000000000000000000000000000000000000000abcd",
+            })
+        );
+        let upper = serde_json::json!({
+            "field": "bn254-fr",
+            "value": "0x000000000000000000000000000000000000000000000000000000000000ABCD",
+        });
+        assert_eq!(Fr::from_json_value(&upper).unwrap(), Fr::from(0xabcdu64));
+
+        // Round trip through text
+        let text = serde_json::to_string(&Fr::from(7u64).to_json_value()).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(Fr::from_json_value(&parsed).unwrap(), Fr::from(7u64));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_value_rejects_invalid() {
+        let mut json = Fr::one().to_json_value();
+        json["field"] = "bls12-381-fr".into();
+        assert_eq!(
+            Fr::from_json_value(&json),
+            Err(JsonError::UnknownField("bls12-381-fr".to_string()))
+        );
+
+        let zeros = "0".repeat(64);
+        for bad in [
+            serde_json::json!("0x01"),
+            serde_json::json!({ "value": format!("0x{}", zeros) }),
+            serde_json::json!({ "field": "bn254-fr" }),
+            serde_json::json!({ "field": "bn254-fr", "value": zeros }),
+            serde_json::json!({ "field": "bn254-fr", "value": "0x01" }),
+            serde_json::json!({ "field": "bn254-fr", "value": format!("0x{}g", &zeros[1..]) }),
+            serde_json::json!({ "field": "bn254-fr", "value": 1 }),
+        ] {
+            assert_eq!(Fr::from_json_value(&bad), Err(JsonError::Malformed));
+        }
+
+        let above = serde_json::json!({
+            "field": "bn254-fr",
+            "value": format!("0x{}", "f".repeat(64)),
+        });
+        assert_eq!(Fr::from_json_value(&above), Err(JsonError::NonCanonical));
+    }
+}
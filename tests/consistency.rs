@@ -0,0 +1,94 @@
+//! Pins the outputs of the software arithmetic to reference vectors computed
+//! once with arbitrary-precision integers, so that any change to the
+//! reduction routines shows up as a failure here. Runs on the host only.
+#![cfg(not(all(target_os = "zkvm", target_vendor = "succinct")))]
+
+use bn254::arithmetic::{mul_wide, reduce_from_wide_limbs};
+use bn254::Fr;
+use ff::{Field, PrimeField};
+use num_bigint::BigUint;
+
+const VECTORS: &str = include_str!("data/fr_reference_vectors.txt");
+
+struct Vector {
+    a: Fr,
+    b: Fr,
+    sum: Fr,
+    difference: Fr,
+    product: Fr,
+    square: Fr,
+    inverse: Fr,
+    wide_product: [u64; 8],
+}
+
+fn parse_fr(decimal: &str) -> Fr {
+    Fr::from_str_vartime(decimal).unwrap_or_else(|| panic!("bad field element {}", decimal))
+}
+
+fn parse_wide(decimal: &str) -> [u64; 8] {
+    let digits = decimal.parse::<BigUint>().unwrap().to_u64_digits();
+    assert!(digits.len() <= 8);
+    let mut limbs = [0u64; 8];
+    limbs[..digits.len()].copy_from_slice(&digits);
+    limbs
+}
+
+fn vectors() -> Vec<Vector> {
+    VECTORS
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(columns.len(), 8, "malformed line {}", line);
+            Vector {
+                a: parse_fr(columns[0]),
+                b: parse_fr(columns[1]),
+                sum: parse_fr(columns[2]),
+                difference: parse_fr(columns[3]),
+                product: parse_fr(columns[4]),
+                square: parse_fr(columns[5]),
+                inverse: parse_fr(columns[6]),
+                wide_product: parse_wide(columns[7]),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_reference_vectors_loaded() {
+    assert_eq!(vectors().len(), 35);
+}
+
+#[test]
+fn test_field_operations_match_reference() {
+    for (i, v) in vectors().iter().enumerate() {
+        assert_eq!(v.a + v.b, v.sum, "vector {}: a + b", i);
+        assert_eq!(v.a - v.b, v.difference, "vector {}: a - b", i);
+        assert_eq!(v.a * v.b, v.product, "vector {}: a * b", i);
+        assert_eq!(v.a.square(), v.square, "vector {}: a^2", i);
+        assert_eq!(
+            v.a.invert().unwrap_or(Fr::ZERO),
+            v.inverse,
+            "vector {}: a^-1",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_wide_arithmetic_matches_reference() {
+    for (i, v) in vectors().iter().enumerate() {
+        assert_eq!(
+            mul_wide(&v.a, &v.b),
+            v.wide_product,
+            "vector {}: mul_wide",
+            i
+        );
+        assert_eq!(
+            reduce_from_wide_limbs(&v.wide_product),
+            v.product,
+            "vector {}: reduce",
+            i
+        );
+    }
+}